@@ -0,0 +1,179 @@
+//! Derive macros for [`livesplit-wrapper`](https://docs.rs/livesplit-wrapper).
+//! Enable the crate's `derive` feature instead of depending on this directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::punctuated::Punctuated;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Expr, ExprLit, Lit, Member, Token};
+
+/// Implements `FromMemory` for a struct, so the whole thing can be read with
+/// one call to `Process::read_struct`. See the docs on `FromMemory` for how
+/// to use it.
+#[proc_macro_derive(FromMemory, attributes(offset))]
+pub fn derive_from_memory(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    from_memory(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Implements `StateSnapshot` for a struct, so it can be watched with a
+/// `StateWatcher`. See the docs on `StateSnapshot` for how to use it.
+#[proc_macro_derive(StateSnapshot, attributes(address))]
+pub fn derive_state_snapshot(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    state_snapshot(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn from_memory(input: DeriveInput) -> syn::Result<TokenStream2> {
+    if input.generics.params.iter().next().is_some() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "FromMemory can't be derived for generic structs",
+        ));
+    }
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "FromMemory can only be derived for structs",
+        ));
+    };
+
+    let krate = quote!(::livesplit_wrapper);
+    let mut consts = Vec::new();
+    let mut ends = Vec::new();
+    let mut inits = Vec::new();
+    let mut previous: Option<(syn::Ident, &syn::Type)> = None;
+    for (i, field) in data.fields.iter().enumerate() {
+        let ty = &field.ty;
+        let name = format_ident!("__OFFSET_{}", i);
+        let mut offset = None;
+        for attr in field.attrs.iter().filter(|a| a.path().is_ident("offset")) {
+            if offset.is_some() {
+                return Err(Error::new_spanned(attr, "duplicate offset"));
+            }
+            offset = Some(attr.parse_args::<Expr>()?);
+        }
+        // fields without an offset come right after the previous one
+        let offset = match (offset, &previous) {
+            (Some(offset), _) => quote!(#offset),
+            (None, Some((prev, prev_ty))) => {
+                quote!(#prev + ::core::mem::size_of::<#prev_ty>())
+            }
+            (None, None) => quote!(0),
+        };
+        consts.push(quote!(const #name: usize = #offset;));
+        ends.push(quote!(#name + ::core::mem::size_of::<#ty>()));
+        let member = match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(i.into()),
+        };
+        inits.push(quote! {
+            #member: #krate::bytemuck::pod_read_unaligned(
+                &buf[#name..#name + ::core::mem::size_of::<#ty>()],
+            )
+        });
+        previous = Some((name, ty));
+    }
+
+    let ident = &input.ident;
+    Ok(quote! {
+        #[automatically_derived]
+        impl #krate::FromMemory for #ident {
+            fn read_from(
+                process: &#krate::Process,
+                addr: #krate::Address,
+            ) -> #krate::Result<Self> {
+                #(#consts)*
+                const __SIZE: usize = {
+                    #[allow(unused_mut)]
+                    let mut size = 0;
+                    #(
+                        let end = #ends;
+                        if end > size {
+                            size = end;
+                        }
+                    )*
+                    size
+                };
+                let mut buf = [0u8; __SIZE];
+                process.read_into_buf(addr, &mut buf)?;
+                Ok(Self { #(#inits,)* })
+            }
+        }
+    })
+}
+
+fn state_snapshot(input: DeriveInput) -> syn::Result<TokenStream2> {
+    if input.generics.params.iter().next().is_some() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "StateSnapshot can't be derived for generic structs",
+        ));
+    }
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "StateSnapshot can only be derived for structs",
+        ));
+    };
+
+    let krate = quote!(::livesplit_wrapper);
+    let mut inits = Vec::new();
+    for (i, field) in data.fields.iter().enumerate() {
+        let ty = &field.ty;
+        let mut attrs = field.attrs.iter().filter(|a| a.path().is_ident("address"));
+        let (Some(attr), None) = (attrs.next(), attrs.next()) else {
+            return Err(Error::new_spanned(
+                field,
+                "every field needs exactly one #[address(...)]",
+            ));
+        };
+        let args = attr.parse_args_with(Punctuated::<Expr, Token![,]>::parse_terminated)?;
+        let mut args = args.iter();
+        // a leading string is the module the address is relative to
+        let module = match args.clone().next() {
+            Some(Expr::Lit(ExprLit {
+                lit: Lit::Str(module),
+                ..
+            })) => {
+                args.next();
+                Some(module)
+            }
+            _ => None,
+        };
+        let Some(addr) = args.next() else {
+            return Err(Error::new_spanned(attr, "missing address"));
+        };
+        let base = match module {
+            Some(module) => quote! {
+                process
+                    .module(#module)
+                    .ok_or_else(|| #krate::Error::ModuleNotFound(#module.into()))?
+                    .wrapping_add(#addr as #krate::Address)
+            },
+            None => quote!(#addr as #krate::Address),
+        };
+        let offsets = args.map(|offset| quote!(#offset as i64));
+        let member = match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(i.into()),
+        };
+        inits.push(quote! {
+            #member: process.read_at_offsets::<#ty>(#base, &[#(#offsets),*])?
+        });
+    }
+
+    let ident = &input.ident;
+    Ok(quote! {
+        #[automatically_derived]
+        impl #krate::StateSnapshot for #ident {
+            fn read(process: &#krate::Process) -> #krate::Result<Self> {
+                Ok(Self { #(#inits,)* })
+            }
+        }
+    })
+}
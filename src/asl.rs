@@ -0,0 +1,285 @@
+use core::time::Duration;
+
+use crate::{
+    Host, HostFunctions, LoadingTimer, Process, RecordStates, Splitter, TimerState, WatchState,
+};
+
+/// A splitter written the way ASL scripts are, as a set of conditions that
+/// get checked every tick, with the timer driven for you. Anything that
+/// implements this is a [`Splitter`], so it can be passed to
+/// [`register_autosplitter!`](crate::register_autosplitter) as usual.
+///
+/// Every tick [`update_state`](Asl::update_state) is called first to read
+/// whatever the conditions need from the game. Then, if a run is going,
+/// [`is_loading`](Asl::is_loading) and [`game_time`](Asl::game_time) are
+/// applied and [`should_reset`](Asl::should_reset) is checked, followed by
+/// [`should_split`](Asl::should_split) if it didn't reset. Before a run
+/// [`should_start`](Asl::should_start) is checked instead, and after one has
+/// ended only `should_reset` is. Every hook except
+/// [`new`](Asl::new) has a default that does nothing.
+///
+/// ```no_run
+/// use livesplit_wrapper::{Asl, GameProcess, Watcher};
+///
+/// struct Celeste {
+///     game: GameProcess,
+///     chapter: Watcher<u32>,
+/// }
+///
+/// impl Asl for Celeste {
+///     fn new() -> Self {
+///         Self {
+///             game: GameProcess::new("Celeste.exe"),
+///             chapter: Watcher::new(0x1000),
+///         }
+///     }
+///
+///     fn update_state(&mut self) -> bool {
+///         let Some(process) = self.game.get() else {
+///             return false;
+///         };
+///         self.chapter.update(process);
+///         true
+///     }
+///
+///     fn should_start(&mut self) -> bool {
+///         self.chapter.changed_to(1)
+///     }
+///
+///     fn should_split(&mut self) -> bool {
+///         self.chapter.increased()
+///     }
+/// }
+///
+/// livesplit_wrapper::register_autosplitter!(Celeste);
+/// # fn main() {}
+/// ```
+pub trait Asl {
+    /// Called when the runtime instantiates your splitter, see
+    /// [`Splitter::new`].
+    fn new() -> Self;
+
+    /// Called at the start of every tick to read the game's state. Returning
+    /// `false` skips the rest of the tick, which is useful while the game
+    /// isn't running.
+    fn update_state(&mut self) -> bool {
+        true
+    }
+
+    /// Whether to start the timer. Only checked while no run is going.
+    fn should_start(&mut self) -> bool {
+        false
+    }
+
+    /// Whether to split.
+    fn should_split(&mut self) -> bool {
+        false
+    }
+
+    /// Whether to reset the run. Like with [`HostFunctions::reset`], be
+    /// conservative with this.
+    fn should_reset(&mut self) -> bool {
+        false
+    }
+
+    /// Whether the game is loading, which pauses game time while it's
+    /// `true`. This is passed on to the timer from
+    /// [`loading_timer`](Asl::loading_timer), so game time is only paused or
+    /// unpaused when it changes, and without one it's ignored. Returning
+    /// `None` leaves game time alone.
+    fn is_loading(&mut self) -> Option<bool> {
+        None
+    }
+
+    /// Where to keep track of whether the game was loading last tick, which
+    /// [`is_loading`](Asl::is_loading) needs to do anything.
+    fn loading_timer(&mut self) -> Option<&mut LoadingTimer> {
+        None
+    }
+
+    /// The game time to show, for games that keep track of their own timer.
+    fn game_time(&mut self) -> Option<Duration> {
+        None
+    }
+
+    /// Called when the timer's state changes, see
+    /// [`Splitter::on_state_change`].
+    fn on_state_change(&mut self, old: TimerState, new: TimerState) {
+        let _ = (old, new);
+    }
+
+    /// Where to record the timer's state changes, see [`Splitter::history`].
+    fn history(&mut self) -> Option<&mut dyn RecordStates> {
+        None
+    }
+
+    /// The state to read before every tick, see [`Splitter::watch_state`].
+    /// It's read before [`update_state`](Asl::update_state) is called.
+    fn watch_state(&mut self) -> Option<(&mut dyn WatchState, &Process)> {
+        None
+    }
+
+    /// Called when a run starts, see [`Splitter::on_timer_start`].
+    fn on_timer_start(&mut self) {}
+
+    /// Called when a run is reset, see [`Splitter::on_timer_reset`].
+    fn on_timer_reset(&mut self) {}
+
+    /// Called after attaching to a process, see
+    /// [`Splitter::on_process_attached`].
+    fn on_process_attached(&mut self) {}
+
+    /// Called once the attached process is gone, see
+    /// [`Splitter::on_process_lost`].
+    fn on_process_lost(&mut self) {}
+}
+
+impl<T: Asl> Splitter for T {
+    fn new() -> Self {
+        <T as Asl>::new()
+    }
+
+    fn update(&mut self) {
+        if !self.update_state() {
+            return;
+        }
+        match self.state() {
+            TimerState::Running | TimerState::Paused => {
+                if let Some(loading) = self.is_loading() {
+                    if let Some(timer) = self.loading_timer() {
+                        timer.set_loading(loading, &Host);
+                    }
+                }
+                if let Some(time) = self.game_time() {
+                    self.set_game_time(time);
+                }
+                if self.should_reset() {
+                    self.reset();
+                } else if self.should_split() {
+                    self.split();
+                }
+            }
+            TimerState::NotRunning => {
+                // a new run starts out unpaused
+                if let Some(timer) = self.loading_timer() {
+                    *timer = LoadingTimer::new();
+                }
+                if self.should_start() {
+                    self.start();
+                }
+            }
+            TimerState::Ended => {
+                if self.should_reset() {
+                    self.reset();
+                }
+            }
+        }
+    }
+
+    fn on_state_change(&mut self, old: TimerState, new: TimerState) {
+        <T as Asl>::on_state_change(self, old, new)
+    }
+
+    fn history(&mut self) -> Option<&mut dyn RecordStates> {
+        <T as Asl>::history(self)
+    }
+
+    fn watch_state(&mut self) -> Option<(&mut dyn WatchState, &Process)> {
+        <T as Asl>::watch_state(self)
+    }
+
+    fn on_timer_start(&mut self) {
+        <T as Asl>::on_timer_start(self)
+    }
+
+    fn on_timer_reset(&mut self) {
+        <T as Asl>::on_timer_reset(self)
+    }
+
+    fn on_process_attached(&mut self) {
+        <T as Asl>::on_process_attached(self)
+    }
+
+    fn on_process_lost(&mut self) {
+        <T as Asl>::on_process_lost(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mock;
+
+    #[derive(Default)]
+    struct Level {
+        level: u32,
+        last: u32,
+        loading: bool,
+        loading_timer: LoadingTimer,
+        running: bool,
+    }
+
+    impl Asl for Level {
+        fn new() -> Self {
+            Self::default()
+        }
+
+        fn update_state(&mut self) -> bool {
+            self.running
+        }
+
+        fn should_start(&mut self) -> bool {
+            self.level == 1
+        }
+
+        fn should_split(&mut self) -> bool {
+            let split = self.level > self.last;
+            self.last = self.level;
+            split
+        }
+
+        fn should_reset(&mut self) -> bool {
+            self.level == 0
+        }
+
+        fn is_loading(&mut self) -> Option<bool> {
+            Some(self.loading)
+        }
+
+        fn loading_timer(&mut self) -> Option<&mut LoadingTimer> {
+            Some(&mut self.loading_timer)
+        }
+    }
+
+    #[test]
+    fn drives_the_timer() {
+        let mut game = <Level as Asl>::new();
+        Splitter::update(&mut game);
+        assert_eq!(game.state(), TimerState::NotRunning);
+
+        game.running = true;
+        game.level = 1;
+        Splitter::update(&mut game);
+        assert_eq!(game.state(), TimerState::Running);
+
+        game.level = 2;
+        game.loading = true;
+        Splitter::update(&mut game);
+        Splitter::update(&mut game);
+        mock::with_host(|h| {
+            assert_eq!(h.splits, 1);
+            assert!(h.game_time_paused);
+            assert_eq!((h.pauses, h.resumes), (1, 0));
+        });
+
+        game.level = 0;
+        game.loading = false;
+        Splitter::update(&mut game);
+        mock::with_host(|h| {
+            assert_eq!(h.resets, 1);
+            assert_eq!(h.splits, 0);
+            assert!(!h.game_time_paused);
+            assert_eq!((h.pauses, h.resumes), (1, 1));
+        });
+    }
+}
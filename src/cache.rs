@@ -0,0 +1,152 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{Address, Pod, Process, Result};
+
+/// Remembers module lookups and pointer paths for the rest of the tick, so
+/// reading a bunch of fields behind the same pointers only follows those
+/// pointers once. Everything is forgotten at the start of the next tick, so
+/// nothing goes stale when the game moves things around.
+///
+/// It's the pointers along the way that are cached, so paths that start out
+/// the same share the work for the part they have in common:
+///
+/// ```no_run
+/// # use livesplit_wrapper::{FrameCache, Process};
+/// # fn f(process: &Process, cache: &mut FrameCache) {
+/// let Some(base) = cache.module(process, "GameAssembly.dll") else {
+///     return;
+/// };
+/// // `base + 0x1A2B3C` and the pointer at offset 0x10 are only read once
+/// let x: f32 = cache
+///     .read_at_offsets(process, base + 0x1A2B3C, &[0x10, 0x40])
+///     .unwrap_or(0.0);
+/// let y: f32 = cache
+///     .read_at_offsets(process, base + 0x1A2B3C, &[0x10, 0x44])
+///     .unwrap_or(0.0);
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct FrameCache {
+    tick: Option<u64>,
+    process: Option<u64>,
+    modules: Vec<(String, Option<Address>)>,
+    pointers: Vec<(Address, Result<Address>)>,
+}
+
+impl FrameCache {
+    /// Create an empty cache.
+    pub const fn new() -> Self {
+        Self {
+            tick: None,
+            process: None,
+            modules: Vec::new(),
+            pointers: Vec::new(),
+        }
+    }
+
+    /// Like [`Process::module`], but only asks the runtime once per tick.
+    pub fn module(&mut self, process: &Process, name: &str) -> Option<Address> {
+        self.refresh(process);
+        if let Some((_, base)) = self.modules.iter().find(|(n, _)| n == name) {
+            return *base;
+        }
+        let base = process.module(name);
+        self.modules.push((name.into(), base));
+        base
+    }
+
+    /// Like [`Process::resolve_offsets`], but each pointer along the way is
+    /// only read once per tick, including when reading it fails.
+    pub fn resolve(
+        &mut self,
+        process: &Process,
+        base: Address,
+        offsets: &[i64],
+    ) -> Result<Address> {
+        self.refresh(process);
+        let mut addr = base;
+        for &offset in offsets {
+            addr = self.pointer(process, addr)?.wrapping_add_signed(offset);
+        }
+        Ok(addr)
+    }
+
+    /// Like [`Process::read_at_offsets`], but following the path through
+    /// the cache. The value itself is read fresh every time.
+    pub fn read_at_offsets<T: Pod>(
+        &mut self,
+        process: &Process,
+        base: Address,
+        offsets: &[i64],
+    ) -> Result<T> {
+        process.read(self.resolve(process, base, offsets)?)
+    }
+
+    fn pointer(&mut self, process: &Process, addr: Address) -> Result<Address> {
+        if let Some((_, pointer)) = self.pointers.iter().find(|(a, _)| *a == addr) {
+            return pointer.clone();
+        }
+        let pointer = process.follow_one(addr);
+        self.pointers.push((addr, pointer.clone()));
+        pointer
+    }
+
+    /// Forget everything if this is a new tick or a different process.
+    fn refresh(&mut self, process: &Process) {
+        let tick = crate::current_tick();
+        if self.tick != Some(tick) || self.process != Some(process.handle()) {
+            self.tick = Some(tick);
+            self.process = Some(process.handle());
+            self.modules.clear();
+            self.pointers.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mock;
+
+    #[test]
+    fn shares_work_within_a_tick() {
+        mock::with_host(|h| {
+            let p = h.spawn(1, "Game.exe");
+            p.load("game.dll", 0x1000, 0x2000u64.to_le_bytes());
+            p.map(0x2010, 0x3000u64.to_le_bytes());
+            p.map(
+                0x3040,
+                [1.0f32.to_le_bytes(), 2.0f32.to_le_bytes()].concat(),
+            );
+        });
+        let p = Process::attach("Game.exe").unwrap();
+        let mut cache = FrameCache::new();
+        crate::__begin_tick();
+        let base = cache.module(&p, "game.dll").unwrap();
+        let lookups = mock::with_host(|h| h.module_lookups);
+        assert_eq!(cache.module(&p, "game.dll"), Some(base));
+        assert_eq!(mock::with_host(|h| h.module_lookups), lookups);
+        assert_eq!(cache.module(&p, "other.dll"), None);
+        let reads = || mock::with_host(|h| h.reads);
+        let before = reads();
+        assert_eq!(cache.read_at_offsets(&p, base, &[0x10, 0x40]), Ok(1.0f32));
+        assert_eq!(reads() - before, 3);
+        // only the value is read, the pointers are shared with the first path
+        let before = reads();
+        assert_eq!(cache.read_at_offsets(&p, base, &[0x10, 0x44]), Ok(2.0f32));
+        assert_eq!(reads() - before, 1);
+        assert_eq!(
+            cache.resolve(&p, base, &[0x10, 0x40, 0]),
+            p.resolve_offsets(base, &[0x10, 0x40, 0])
+        );
+        let before = reads();
+        let _ = cache.resolve(&p, base, &[0x10, 0x40, 0]);
+        assert_eq!(reads(), before);
+
+        // a new tick reads everything again
+        mock::with_host(|h| h.process(1).write(0x2010, &0x3004u64.to_le_bytes()));
+        crate::__begin_tick();
+        assert_eq!(cache.read_at_offsets(&p, base, &[0x10, 0x40]), Ok(2.0f32));
+    }
+}
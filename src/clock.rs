@@ -0,0 +1,91 @@
+use core::time::Duration;
+
+/// `Instant` panics on `wasm32-unknown-unknown`, which is the usual target
+/// for autosplitters, so it's only used where it actually works.
+#[cfg(all(
+    feature = "std",
+    not(all(target_family = "wasm", target_os = "unknown"))
+))]
+type Instant = std::time::Instant;
+
+/// Measures how much time passes between updates, which isn't always what the
+/// tick rate says since the runtime can fall behind. The clock driving
+/// [`register_autosplitter!`](crate::register_autosplitter) can be read with
+/// [`tick_delta`](crate::HostFunctions::tick_delta), so you'll only need your
+/// own if you want to time something else.
+///
+/// Where there's no clock to read (without `std`, or on
+/// `wasm32-unknown-unknown`) this falls back to assuming every tick took
+/// exactly as long as the [current tick
+/// rate](crate::HostFunctions::current_tick_rate) says it should.
+#[derive(Debug, Clone)]
+pub struct TickClock {
+    #[cfg(all(
+        feature = "std",
+        not(all(target_family = "wasm", target_os = "unknown"))
+    ))]
+    last: Option<Instant>,
+    delta: Duration,
+}
+
+impl TickClock {
+    /// Create a clock that hasn't been ticked yet.
+    pub const fn new() -> Self {
+        Self {
+            #[cfg(all(
+                feature = "std",
+                not(all(target_family = "wasm", target_os = "unknown"))
+            ))]
+            last: None,
+            delta: Duration::ZERO,
+        }
+    }
+
+    /// Mark the start of an update. The first tick has nothing to measure
+    /// from, so it uses the tick rate like the fallback does.
+    pub fn tick(&mut self) {
+        #[cfg(all(
+            feature = "std",
+            not(all(target_family = "wasm", target_os = "unknown"))
+        ))]
+        {
+            let now = Instant::now();
+            if let Some(last) = self.last.replace(now) {
+                self.delta = now - last;
+                return;
+            }
+        }
+        self.delta = Duration::from_secs_f64(1.0 / crate::TICK_RATE.get());
+    }
+
+    /// How long it's been between the last two ticks.
+    pub fn delta(&self) -> Duration {
+        self.delta
+    }
+}
+
+impl Default for TickClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Host, HostFunctions};
+
+    #[test]
+    fn measures_between_ticks() {
+        let mut clock = TickClock::new();
+        Host.set_tick_rate(50.0);
+        clock.tick();
+        assert_eq!(clock.delta(), Duration::from_millis(20));
+        std::thread::sleep(Duration::from_millis(30));
+        clock.tick();
+        #[cfg(feature = "std")]
+        assert!(clock.delta() >= Duration::from_millis(30));
+        #[cfg(not(feature = "std"))]
+        assert_eq!(clock.delta(), Duration::from_millis(20));
+    }
+}
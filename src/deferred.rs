@@ -0,0 +1,110 @@
+/// A value that takes a few tries to get, like a module that hasn't loaded yet
+/// or a signature that isn't there until the game has started up. Each tick
+/// [`get_or_retry`](Deferred::get_or_retry) tries again until it works, and
+/// from then on the value is just handed back, which saves keeping an
+/// `Option` around and checking it by hand.
+///
+/// ```no_run
+/// # use livesplit_wrapper::{Address, Deferred, Process};
+/// # fn f(process: &Process, base: &mut Deferred<Address>) {
+/// let Some(&base) = base.get_or_retry(|| process.module("game.dll")) else {
+///     return;
+/// };
+/// # }
+/// ```
+///
+/// Nothing notices when the value stops being right, so
+/// [`reset`](Deferred::reset) it when the game restarts, for example when
+/// [`GameProcess::just_attached`](crate::GameProcess::just_attached) is
+/// `true`.
+#[derive(Debug, Clone)]
+pub struct Deferred<T> {
+    value: Option<T>,
+    retry_interval: u64,
+    last_try: Option<u64>,
+}
+
+impl<T> Deferred<T> {
+    /// Create a value that hasn't been gotten yet, which is tried for on
+    /// every tick.
+    pub const fn new() -> Self {
+        Self {
+            value: None,
+            retry_interval: 1,
+            last_try: None,
+        }
+    }
+
+    /// Only try once every `ticks` ticks, for things that are slow to look
+    /// for, like scanning for a signature.
+    pub fn with_retry_interval(mut self, ticks: u64) -> Self {
+        self.retry_interval = ticks.max(1);
+        self
+    }
+
+    /// The value, calling `init` to try to get it if that hasn't worked yet
+    /// and it's time to try again. Once `init` returns `Some` it's never
+    /// called again until a [`reset`](Deferred::reset).
+    pub fn get_or_retry(&mut self, init: impl FnOnce() -> Option<T>) -> Option<&T> {
+        if self.value.is_none() {
+            let tick = crate::current_tick();
+            let due = self
+                .last_try
+                .is_none_or(|last| tick - last >= self.retry_interval);
+            if due {
+                self.last_try = Some(tick);
+                self.value = init();
+            }
+        }
+        self.value.as_ref()
+    }
+
+    /// The value, if it's been gotten.
+    pub fn get(&self) -> Option<&T> {
+        self.value.as_ref()
+    }
+
+    /// Forget the value so it's looked for again on the next call to
+    /// [`get_or_retry`](Deferred::get_or_retry).
+    pub fn reset(&mut self) {
+        self.value = None;
+        self.last_try = None;
+    }
+}
+
+impl<T> Default for Deferred<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn retries_until_ready() {
+        let mut deferred = Deferred::new().with_retry_interval(2);
+        let mut tries = 0;
+        let mut attempt = |ready: bool| {
+            crate::__begin_tick();
+            deferred
+                .get_or_retry(|| {
+                    tries += 1;
+                    ready.then_some(5)
+                })
+                .copied()
+        };
+        assert_eq!(attempt(false), None);
+        // too soon to try again
+        assert_eq!(attempt(true), None);
+        assert_eq!(attempt(true), Some(5));
+        assert_eq!(attempt(false), Some(5));
+        assert_eq!(tries, 2);
+
+        deferred.reset();
+        assert_eq!(deferred.get(), None);
+        crate::__begin_tick();
+        assert_eq!(deferred.get_or_retry(|| Some(6)), Some(&6));
+    }
+}
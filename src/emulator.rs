@@ -0,0 +1,256 @@
+//! Reading the memory of games running in an emulator.
+//!
+//! Games in an emulator use addresses from the console's memory map, which
+//! have to be translated into wherever the emulator put the console's RAM in
+//! its own memory. Once that's been found, an [`Emulator`] does the
+//! translation and reads values in the console's byte order.
+//!
+//! Finding the RAM depends on the emulator. PCSX2 exports a pointer to it,
+//! see [`Emulator::pcsx2`], and Dolphin maps it in a range of its own, see
+//! [`Emulator::dolphin`]. For other emulators the usual approach is to find
+//! the code that refers to the RAM with [`Emulator::from_signature`].
+//!
+//! RetroArch and BizHawk aren't supported directly. The RAM belongs to
+//! whichever core is loaded, and each core keeps it somewhere different, so
+//! the module and signature to use depend on the core and have to be passed
+//! to `from_signature`.
+
+use crate::{Address, Endian, Error, Process, Result, SwapBytes};
+
+/// Where a console's main RAM is in its memory map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Console {
+    /// The console address of the first byte of RAM.
+    pub base: Address,
+    /// The size of RAM in bytes.
+    pub size: u64,
+    /// The console's byte order.
+    pub endian: Endian,
+}
+
+impl Console {
+    /// The GameCube's 24MiB of main RAM, which is also the Wii's MEM1.
+    pub const GAMECUBE: Console = Console {
+        base: 0x8000_0000,
+        size: 0x180_0000,
+        endian: Endian::Big,
+    };
+
+    /// The PlayStation's 2MiB of main RAM, as seen through KSEG0.
+    pub const PS1: Console = Console {
+        base: 0x8000_0000,
+        size: 0x20_0000,
+        endian: Endian::Little,
+    };
+
+    /// The PlayStation 2's 32MiB of Emotion Engine RAM.
+    pub const PS2: Console = Console {
+        base: 0,
+        size: 0x200_0000,
+        endian: Endian::Little,
+    };
+}
+
+/// A console's RAM inside an emulator's memory.
+///
+/// ```no_run
+/// # use livesplit_wrapper::{emulator::Emulator, Process};
+/// # fn f(process: &Process) -> Option<()> {
+/// let emu = Emulator::pcsx2(process)?;
+/// let level: u32 = emu.read(process, 0x003F_1A2C).ok()?;
+/// # Some(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Emulator {
+    console: Console,
+    ram: Address,
+}
+
+impl Emulator {
+    /// The `console`'s RAM starts at `ram` in the emulator's memory.
+    pub const fn new(console: Console, ram: Address) -> Self {
+        Self { console, ram }
+    }
+
+    /// PCSX2, which exports a pointer to the Emotion Engine's RAM called
+    /// `EEmem` from its executable since 1.7. Versions before that don't, but
+    /// always map the RAM at `0x20000000`, so that's used if all 32MiB of it
+    /// is readable. Returns `None` if the RAM can't be found either way.
+    pub fn pcsx2(process: &Process) -> Option<Self> {
+        const OLD_EE_MEMORY: Address = 0x2000_0000;
+        let exported = process
+            .main_module()
+            .and_then(|base| process.pe_export(base, "EEmem"));
+        let ram = match exported {
+            Some(ee_mem) => process.read_pointer(ee_mem, process.pointer_width()).ok()?,
+            None => OLD_EE_MEMORY,
+        };
+        let last = ram + Console::PS2.size - 1;
+        (process.read::<u8>(ram).is_ok() && process.read::<u8>(last).is_ok())
+            .then(|| Self::new(Console::PS2, ram))
+    }
+
+    /// Dolphin, which maps the GameCube's RAM (or the Wii's MEM1) in a 32MiB
+    /// range of its own. The range is recognized by its size and by the
+    /// game's ID at the start of RAM, so this returns `None` until a game
+    /// has booted. This looks through every range of memory, so only do it
+    /// once and keep the result. Needs the `memory-ranges` feature.
+    #[cfg(any(test, feature = "memory-ranges"))]
+    pub fn dolphin(process: &Process) -> Option<Self> {
+        use crate::MemoryRangeFlags;
+        const MEM1_SIZE: u64 = 0x200_0000;
+        process
+            .memory_ranges()
+            .into_iter()
+            .filter(|range| {
+                range.size == MEM1_SIZE && !range.flags.contains(MemoryRangeFlags::PATH)
+            })
+            .find(|range| {
+                // games start with their 6 character ID, like GALE01
+                process
+                    .read::<[u8; 6]>(range.base)
+                    .is_ok_and(|id| id.iter().all(u8::is_ascii_alphanumeric))
+            })
+            .map(|range| Self::new(Console::GAMECUBE, range.base))
+    }
+
+    /// Find the RAM through a pointer to it, which is read from `offset`
+    /// bytes past the first match of a
+    /// [signature](Process::scan_signature) in `module`. Pointers are read
+    /// with the process's [`pointer_width`](Process::pointer_width).
+    pub fn from_signature(
+        process: &Process,
+        console: Console,
+        module: &str,
+        pattern: &str,
+        offset: i64,
+    ) -> Option<Self> {
+        let found = process.scan_signature(module, pattern)?;
+        let ram = process
+            .read_pointer(found.wrapping_add_signed(offset), process.pointer_width())
+            .ok()?;
+        process
+            .is_likely_valid(ram)
+            .then(|| Self::new(console, ram))
+    }
+
+    /// Where the console's RAM starts in the emulator's memory.
+    pub fn ram(&self) -> Address {
+        self.ram
+    }
+
+    /// The console this is emulating.
+    pub fn console(&self) -> Console {
+        self.console
+    }
+
+    /// Translate a console address into an address in the emulator, or
+    /// `None` if it isn't in RAM.
+    pub fn to_host(&self, console_addr: Address) -> Option<Address> {
+        let offset = console_addr.checked_sub(self.console.base)?;
+        (offset < self.console.size).then(|| self.ram + offset)
+    }
+
+    /// Read a value at a console address, in the console's byte order.
+    /// Addresses outside of RAM give an [`Error::InvalidPointer`].
+    pub fn read<T: SwapBytes>(&self, process: &Process, console_addr: Address) -> Result<T> {
+        let addr = self
+            .to_host(console_addr)
+            .ok_or(Error::InvalidPointer(console_addr))?;
+        match self.console.endian {
+            Endian::Little => process.read_le(addr),
+            Endian::Big => process.read_be(addr),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mock;
+    use alloc::vec;
+
+    #[test]
+    fn translates_console_addresses() {
+        let mut ee_memory = vec![0; 0x200_0000];
+        ee_memory[0x10_0000..0x10_0004].copy_from_slice(&7u32.to_le_bytes());
+        mock::with_host(|h| {
+            let p = h.spawn(1, "pcsx2.exe");
+            p.map(0x2000_0000, ee_memory);
+            let p = h.spawn(2, "Dolphin.exe");
+            p.load(
+                "Dolphin.exe",
+                0x1000,
+                *b"\x48\x8B\x0D\0\0\0\0\x00\x00\x00\x10\0\0\0\0",
+            );
+            p.map(0x1000_1234, 5u16.to_be_bytes());
+        });
+        let pcsx2 = Process::attach("pcsx2.exe").unwrap();
+        let emu = Emulator::pcsx2(&pcsx2).unwrap();
+        assert_eq!(emu.read::<u32>(&pcsx2, 0x10_0000), Ok(7));
+        assert_eq!(
+            emu.read::<u32>(&pcsx2, 0x200_0000),
+            Err(Error::InvalidPointer(0x200_0000))
+        );
+
+        let dolphin = Process::attach("Dolphin.exe").unwrap();
+        assert_eq!(Emulator::pcsx2(&dolphin), None);
+        let emu =
+            Emulator::from_signature(&dolphin, Console::GAMECUBE, "Dolphin.exe", "48 8B 0D", 7)
+                .unwrap();
+        assert_eq!(emu.ram(), 0x1000_0000);
+        assert_eq!(emu.to_host(0x8000_1234), Some(0x1000_1234));
+        assert_eq!(emu.to_host(0x7FFF_FFFF), None);
+        assert_eq!(emu.read::<u16>(&dolphin, 0x8000_1234), Ok(5));
+    }
+
+    #[test]
+    fn finds_exported_pcsx2_ram() {
+        let mut exe = mock::Memory::new(0x400);
+        exe.pe_export("EEmem", 0x380).ptr(0x380, 0x7FF6_0000_0000);
+        let mut ee_memory = vec![0; 0x200_0000];
+        ee_memory[0x1234..0x1238].copy_from_slice(&3u32.to_le_bytes());
+        mock::with_host(|h| {
+            let p = h.spawn(1, "pcsx2-qt.exe");
+            p.load("pcsx2-qt.exe", 0x1_4000_0000, exe.0);
+            p.map(0x7FF6_0000_0000, ee_memory);
+            // not the RAM, even though it's where older versions put it
+            p.map(0x2000_0000, [0; 0x10]);
+        });
+        let pcsx2 = Process::attach("pcsx2-qt.exe").unwrap();
+        let emu = Emulator::pcsx2(&pcsx2).unwrap();
+        assert_eq!(emu.ram(), 0x7FF6_0000_0000);
+        assert_eq!(emu.read::<u32>(&pcsx2, 0x1234), Ok(3));
+
+        mock::with_host(|h| {
+            let p = h.spawn(2, "pcsx2.exe");
+            p.map(0x2000_0000, [0; 0x10]);
+        });
+        let old = Process::attach("pcsx2.exe").unwrap();
+        assert_eq!(Emulator::pcsx2(&old), None);
+    }
+
+    #[test]
+    fn finds_dolphin_ram() {
+        let mut mem1 = vec![0; 0x200_0000];
+        mem1[..6].copy_from_slice(b"GALE01");
+        mem1[0x1234..0x1238].copy_from_slice(&9u32.to_be_bytes());
+        mock::with_host(|h| {
+            let p = h.spawn(1, "Dolphin.exe");
+            p.load("Dolphin.exe", 0x1000, [0; 0x10]);
+            // the wrong size, and the right size but before a game booted
+            p.map(0x1_0000_0000, vec![b'G'; 0x100_0000]);
+            p.map(0x2_0000_0000, vec![0; 0x200_0000]);
+            p.map(0x3_0000_0000, mem1);
+        });
+        let dolphin = Process::attach("Dolphin.exe").unwrap();
+        let emu = Emulator::dolphin(&dolphin).unwrap();
+        assert_eq!(emu.ram(), 0x3_0000_0000);
+        assert_eq!(emu.console(), Console::GAMECUBE);
+        assert_eq!(emu.read::<u32>(&dolphin, 0x8000_1234), Ok(9));
+
+        mock::with_host(|h| h.process(1).write(0x3_0000_0000, &[0; 6]));
+        assert_eq!(Emulator::dolphin(&dolphin), None);
+    }
+}
@@ -0,0 +1,87 @@
+use core::fmt::{self, Write};
+use core::time::Duration;
+
+/// A [`fmt::Write`] sink backed by a fixed size stack buffer so formatting
+/// doesn't need to allocate. Output that doesn't fit is truncated at the last
+/// full character rather than growing the buffer.
+pub(crate) struct FixedBuf<const N: usize> {
+    buf: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> FixedBuf<N> {
+    pub(crate) const fn new() -> Self {
+        Self {
+            buf: [0; N],
+            len: 0,
+        }
+    }
+
+    pub(crate) fn as_str(&self) -> &str {
+        // SAFETY: `write_str` only ever copies whole chars from valid `str`s
+        unsafe { core::str::from_utf8_unchecked(&self.buf[..self.len]) }
+    }
+
+    /// Replace the end of the output with `marker` so it's obvious that
+    /// something got cut off, backing up to a char boundary to make room.
+    pub(crate) fn mark_truncated(&mut self, marker: &str) {
+        let mut len = self.len.min(N.saturating_sub(marker.len()));
+        while !self.as_str().is_char_boundary(len) {
+            len -= 1;
+        }
+        self.len = len;
+        self.write_str(marker).ok();
+    }
+}
+
+impl<const N: usize> Write for FixedBuf<N> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut n = s.len().min(N - self.len);
+        while !s.is_char_boundary(n) {
+            n -= 1;
+        }
+        self.buf[self.len..self.len + n].copy_from_slice(&s.as_bytes()[..n]);
+        self.len += n;
+        // stop the formatter early once we're out of space
+        (n == s.len()).then_some(()).ok_or(fmt::Error)
+    }
+}
+
+/// Shows a duration like a timer does, as `mm:ss.xxx`, with the hours in
+/// front once there are any.
+pub(crate) struct Clock(pub(crate) Duration);
+
+impl fmt::Display for Clock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let secs = self.0.as_secs();
+        let (hours, minutes, secs) = (secs / 3600, secs / 60 % 60, secs % 60);
+        let millis = self.0.subsec_millis();
+        if hours > 0 {
+            write!(f, "{hours}:")?;
+        }
+        write!(f, "{minutes:02}:{secs:02}.{millis:03}")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn truncates_on_char_boundary() {
+        let mut buf = FixedBuf::<8>::new();
+        assert!(write!(buf, "{}", 1234).is_ok());
+        assert!(write!(buf, "ab✨cd").is_err());
+        assert_eq!(buf.as_str(), "1234ab");
+        buf.mark_truncated("…");
+        assert_eq!(buf.as_str(), "1234a…");
+    }
+
+    #[test]
+    fn clock() {
+        let clock = |millis| alloc::format!("{}", Clock(Duration::from_millis(millis)));
+        assert_eq!(clock(65_250), "01:05.250");
+        assert_eq!(clock(999), "00:00.999");
+        assert_eq!(clock(3_723_004), "1:02:03.004");
+    }
+}
@@ -0,0 +1,105 @@
+use core::marker::PhantomData;
+#[cfg(not(any(test, feature = "testing")))]
+use core::sync::atomic::{AtomicU64, Ordering::Relaxed};
+
+/// A global value shared by everything in the splitter. The runtime only ever
+/// calls into the module from one thread so an atomic is all this needs to be,
+/// but tests run in parallel so each test thread sees its own copy.
+pub(crate) struct Global<T> {
+    #[cfg(not(any(test, feature = "testing")))]
+    value: AtomicU64,
+    #[cfg(any(test, feature = "testing"))]
+    init: u64,
+    _type: PhantomData<T>,
+}
+
+/// Types that can be stored in a [`Global`].
+pub(crate) trait Bits: Copy {
+    fn to_bits(self) -> u64;
+    fn from_bits(bits: u64) -> Self;
+}
+
+impl Bits for u64 {
+    fn to_bits(self) -> u64 {
+        self
+    }
+
+    fn from_bits(bits: u64) -> Self {
+        bits
+    }
+}
+
+impl Bits for f64 {
+    fn to_bits(self) -> u64 {
+        f64::to_bits(self)
+    }
+
+    fn from_bits(bits: u64) -> Self {
+        f64::from_bits(bits)
+    }
+}
+
+impl Bits for bool {
+    fn to_bits(self) -> u64 {
+        self as u64
+    }
+
+    fn from_bits(bits: u64) -> Self {
+        bits != 0
+    }
+}
+
+impl Bits for log::LevelFilter {
+    fn to_bits(self) -> u64 {
+        self as u64
+    }
+
+    fn from_bits(bits: u64) -> Self {
+        log::LevelFilter::iter()
+            .nth(bits as usize)
+            .unwrap_or(log::LevelFilter::Trace)
+    }
+}
+
+#[cfg(any(test, feature = "testing"))]
+std::thread_local! {
+    static LOCAL: core::cell::RefCell<std::collections::HashMap<usize, u64>> =
+        Default::default();
+}
+
+impl<T: Bits> Global<T> {
+    /// Create a global starting out with the value `bits` represents (trait
+    /// methods can't be called in a `const fn`, so the conversion is up to the
+    /// caller).
+    pub(crate) const fn new(bits: u64) -> Self {
+        Self {
+            #[cfg(not(any(test, feature = "testing")))]
+            value: AtomicU64::new(bits),
+            #[cfg(any(test, feature = "testing"))]
+            init: bits,
+            _type: PhantomData,
+        }
+    }
+
+    #[cfg(not(any(test, feature = "testing")))]
+    pub(crate) fn get(&self) -> T {
+        T::from_bits(self.value.load(Relaxed))
+    }
+
+    #[cfg(not(any(test, feature = "testing")))]
+    pub(crate) fn set(&self, value: T) {
+        self.value.store(value.to_bits(), Relaxed);
+    }
+
+    #[cfg(any(test, feature = "testing"))]
+    pub(crate) fn get(&self) -> T {
+        let key = self as *const Self as usize;
+        T::from_bits(LOCAL.with(|l| l.borrow().get(&key).copied().unwrap_or(self.init)))
+    }
+
+    #[cfg(any(test, feature = "testing"))]
+    pub(crate) fn set(&self, value: T) {
+        let key = self as *const Self as usize;
+        LOCAL.with(|l| l.borrow_mut().insert(key, value.to_bits()));
+    }
+}
@@ -1,33 +1,172 @@
+#![cfg_attr(not(feature = "std"), no_std)]
 #![deny(missing_docs)]
 #![doc = include_str!("../README.md")]
 #![doc(html_logo_url = "https://github.com/LiveSplit.png")]
 
+extern crate alloc;
+// lets the derive macros refer to the crate by name in its own tests
+#[cfg(test)]
+extern crate self as livesplit_wrapper;
+#[cfg(all(test, not(feature = "std")))]
+extern crate std;
+
+mod asl;
+mod cache;
+mod clock;
+mod deferred;
+pub mod emulator;
+mod fmt;
+mod global;
+#[cfg(any(test, feature = "testing"))]
+mod mock;
+mod pointer;
 mod process;
-use std::time::Duration;
+pub mod runtime;
+mod scratch;
+pub mod settings;
+mod state;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+mod throttle;
+pub mod time;
+mod timer;
+pub mod unity;
+pub mod unreal;
+mod variables;
+mod version;
+mod view;
+mod watcher;
+use core::fmt::{Display, Write};
+use core::time::Duration;
 
+pub use asl::Asl;
+#[doc(hidden)]
+pub use bytemuck;
+pub use cache::FrameCache;
+pub use clock::TickClock;
+pub use deferred::Deferred;
+#[cfg(feature = "derive")]
+pub use livesplit_wrapper_derive::{FromMemory, StateSnapshot};
+#[doc(hidden)]
+pub use log;
+#[cfg(feature = "std")]
 pub use once_cell::sync::OnceCell;
-pub use process::{Address, Error, Pod, Process, Result};
+pub use pointer::{DeepPointer, Pointers};
+pub use process::{
+    Address, Endian, Error, FromMemory, GameProcess, MemoryRange, MemoryRangeFlags, Module,
+    ModuleWatcher, MonoStringLayout, OnceProcess, Pod, PointerWidth, Process, Result, SwapBytes,
+    Zeroable,
+};
+pub use scratch::ScratchBuffer;
+pub use state::{StateSnapshot, StateWatcher, WatchState};
+#[doc(hidden)]
+pub use throttle::__Throttle;
+pub use timer::{
+    AutoStart, Debounced, IgtTimer, LoadRemover, LoadingTimer, RecordStates, ResetGuard,
+    SplitManager, SplitPolicy, SplitSequence, StateHistory, TimerControl, Transition,
+};
+pub use variables::{Variable, VariableBatch};
+pub use version::VersionDetector;
+pub use view::StructView;
+pub use watcher::{Watcher, WatcherSet};
+
+use global::Global;
+use log::{Level, LevelFilter, Metadata, Record};
+
+#[cfg(any(test, feature = "testing"))]
+use mock as ffi;
 
-use log::{Level, Metadata, Record};
+/// The tick rate that LiveSplit uses until the splitter asks for a different
+/// one.
+pub const DEFAULT_TICK_RATE: f64 = 120.0;
+
+static TICK: Global<u64> = Global::new(0);
+static TICK_RATE: Global<f64> = Global::new(DEFAULT_TICK_RATE.to_bits());
+static TICK_DELTA: Global<f64> = Global::new((1.0 / DEFAULT_TICK_RATE).to_bits());
+static LOGGING_ENABLED: Global<bool> = Global::new(1);
+static MAX_LOG_LEVEL: Global<LevelFilter> = Global::new(LevelFilter::Info as u64);
+/// The handle of the process attached to most recently, or 0 once that
+/// process has been detached.
+static LAST_ATTACHED: Global<u64> = Global::new(0);
+#[cfg(feature = "std")]
+static PANIC_STREAK: Global<u64> = Global::new(0);
+
+/// Called by [`register_autosplitter!`] at the start of every update.
+#[doc(hidden)]
+pub fn __begin_tick() {
+    TICK.set(TICK.get() + 1);
+}
+
+/// The number of updates that have started so far, used by helpers that should
+/// only do something once per tick.
+pub(crate) fn current_tick() -> u64 {
+    TICK.get()
+}
 
 /// This logger gets initialized automatically when you register an autosplitter
-/// and emits logs to LiveSplit's autosplitter runtime.
-pub struct Logger;
+/// and emits logs to LiveSplit's autosplitter runtime. Each message is prefixed
+/// with the module it was logged from, and the splitter's name if one was
+/// given to [`register_autosplitter!`]. Key-value pairs attached to a message
+/// (like `log::info!(level = 3; "entered level")`) are put after it as
+/// `key=value`. Only messages at or above the logger's level are shown, which
+/// is `Info` unless it was given with [`with_level`](Logger::with_level) (or
+/// the `level` option of [`register_autosplitter!`]), and can be changed
+/// while running with [`HostFunctions::set_log_level`].
+pub struct Logger {
+    name: Option<&'static str>,
+    level: LevelFilter,
+}
+
+impl Logger {
+    /// Create a logger that puts `name` in front of every message.
+    pub const fn new(name: Option<&'static str>) -> Self {
+        Self {
+            name,
+            level: LevelFilter::Info,
+        }
+    }
+
+    /// Start out showing messages at or above `level` instead of `Info`.
+    pub const fn with_level(mut self, level: LevelFilter) -> Self {
+        self.level = level;
+        self
+    }
+}
 
 impl log::Log for Logger {
     fn enabled(&self, metadata: &Metadata) -> bool {
-        metadata.level() <= Level::Info
+        LOGGING_ENABLED.get() && metadata.level() <= MAX_LOG_LEVEL.get()
     }
 
     fn log(&self, record: &Record) {
         if self.enabled(record.metadata()) {
-            // TODO: fixed size formatter to avoid alloc?
-            let s = match record.level() {
-                Level::Info => format!("{}", record.args()),
-                Level::Warn => format!("⚠️ {}", record.args()),
-                Level::Error => format!("⛔ {}", record.args()),
-                _ => unimplemented!(),
+            let level = match record.level() {
+                Level::Info => "",
+                Level::Warn => "⚠️ ",
+                Level::Error => "⛔ ",
+                Level::Debug => "🐞 ",
+                Level::Trace => "🔍 ",
             };
+            let mut buf = fmt::FixedBuf::<LOG_BUFFER_SIZE>::new();
+            let written = match self.name {
+                Some(name) => write!(
+                    buf,
+                    "{level}[{name}] {}: {}",
+                    record.target(),
+                    record.args()
+                ),
+                None => write!(buf, "{level}{}: {}", record.target(), record.args()),
+            }
+            .and_then(|()| {
+                record
+                    .key_values()
+                    .visit(&mut KeyValues(&mut buf))
+                    .map_err(|_| core::fmt::Error)
+            });
+            if written.is_err() {
+                buf.mark_truncated("…");
+            }
+            let s = buf.as_str();
             unsafe { ffi::runtime_print_message(s.as_ptr(), s.len()) }
         }
     }
@@ -35,48 +174,301 @@ impl log::Log for Logger {
     fn flush(&self) {}
 }
 
+/// Messages are formatted on the stack so logging doesn't allocate, and get
+/// cut off with a `…` if they don't fit.
+const LOG_BUFFER_SIZE: usize = 1024;
+
+struct KeyValues<'a, const N: usize>(&'a mut fmt::FixedBuf<N>);
+
+impl<'kvs, const N: usize> log::kv::VisitSource<'kvs> for KeyValues<'_, N> {
+    fn visit_pair(
+        &mut self,
+        key: log::kv::Key<'kvs>,
+        value: log::kv::Value<'kvs>,
+    ) -> core::result::Result<(), log::kv::Error> {
+        write!(self.0, " {key}={value}").map_err(Into::into)
+    }
+}
+
 /// Wires up the necessary c interface for a type that implements [`Splitter`].
 ///
 /// If you defined `struct MySplitter {...}` and `impl Splitter for MySplitter
 /// {...}` then you can write `register_autosplitter!(MySplitter);` and you'll
-/// be good to go.
+/// be good to go. You can also give your splitter a name, like
+/// `register_autosplitter!(MySplitter, "Celeste")`, which will be shown in
+/// front of everything it logs.
+///
+/// Other options are given as `key = value` pairs after the splitter:
+///
+/// - `name`: the same name as above.
+/// - `on_panic`: what to do when [`update`](Splitter::update) panics, see
+///   [`PanicStrategy`].
+/// - `level`: the least severe `log::LevelFilter` that's shown, which is `Info`
+///   by default. It can still be changed later with
+///   [`set_log_level`](HostFunctions::set_log_level).
+///
+/// ```no_run
+/// # use livesplit_wrapper::{PanicStrategy, Splitter};
+/// # struct MySplitter;
+/// # impl Splitter for MySplitter {
+/// #     fn new() -> Self { MySplitter }
+/// #     fn update(&mut self) {}
+/// # }
+/// livesplit_wrapper::register_autosplitter!(
+///     MySplitter,
+///     name = "Celeste",
+///     on_panic = PanicStrategy::Abort,
+///     level = log::LevelFilter::Debug,
+/// );
+/// # fn main() {}
+/// ```
+///
+/// This can only be used once per crate, and using it again (even in another
+/// module) fails with an error that the symbol
+/// `register_autosplitter_can_only_be_used_once` is already defined:
+///
+/// ```compile_fail
+/// # use livesplit_wrapper::Splitter;
+/// # struct MySplitter;
+/// # impl Splitter for MySplitter {
+/// #     fn new() -> Self { MySplitter }
+/// #     fn update(&mut self) {}
+/// # }
+/// mod one {
+///     use super::MySplitter;
+///     livesplit_wrapper::register_autosplitter!(MySplitter);
+/// }
+/// mod two {
+///     use super::MySplitter;
+///     livesplit_wrapper::register_autosplitter!(MySplitter);
+/// }
+/// # fn main() {}
+/// ```
 #[macro_export]
 macro_rules! register_autosplitter {
-    ($struct:ident) => {
-        use std::panic;
-        // TODO: make sure mutex is a nop in wasm
-        use std::sync::Mutex;
-
-        use $crate::{Logger, OnceCell};
+    ($struct:ident $(, $option:ident = $value:expr)* $(,)?) => {
+        // registering twice makes this symbol collide, which gives an error that
+        // actually explains the problem instead of just complaining about `update`
+        #[no_mangle]
+        #[allow(non_upper_case_globals)]
+        static register_autosplitter_can_only_be_used_once: () = ();
 
-        const LOGGER: Logger = Logger;
-        static SINGLETON: OnceCell<Mutex<$struct>> = OnceCell::new();
+        static SINGLETON: $crate::__Registration<$struct> =
+            $crate::__Registration::new()$(.$option($value))*;
 
         #[no_mangle]
         pub extern "C" fn update() {
-            SINGLETON
-                .get_or_init(|| {
-                    log::set_logger(&LOGGER)
-                        .map(|()| log::set_max_level(log::LevelFilter::Info))
-                        .ok();
-                    panic::set_hook(Box::new(|panic_info| {
-                        if let Some(location) = panic_info.location() {
-                            log::error!(
-                                "panic occurred in file '{}' at line {}",
-                                location.file(),
-                                location.line(),
-                            );
-                        } else {
-                            log::error!("panic occurred but can't get location information...");
-                        }
-                    }));
-                    Mutex::new($struct::new())
-                })
-                .lock()
-                .unwrap()
-                .update();
+            SINGLETON.update();
         }
     };
+    ($struct:ident, $name:expr) => {
+        $crate::register_autosplitter!($struct, name = $name);
+    };
+}
+
+/// The storage for a registered splitter, which gets created lazily on the
+/// first update. This is an implementation detail of
+/// [`register_autosplitter!`].
+#[doc(hidden)]
+pub struct __Registration<S> {
+    logger: Logger,
+    #[cfg_attr(not(feature = "std"), allow(dead_code))]
+    on_panic: PanicStrategy,
+    #[cfg(feature = "std")]
+    // TODO: make sure mutex is a nop in wasm
+    runner: OnceCell<std::sync::Mutex<Runner<S>>>,
+    #[cfg(not(feature = "std"))]
+    runner: core::cell::UnsafeCell<Option<Runner<S>>>,
+}
+
+// SAFETY: without `std` the only supported target is single threaded wasm, so
+// there's never more than one reference to the splitter at a time
+#[cfg(not(feature = "std"))]
+unsafe impl<S> Sync for __Registration<S> {}
+
+impl<S: Splitter> __Registration<S> {
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        Self {
+            logger: Logger::new(None),
+            on_panic: PanicStrategy::Continue,
+            #[cfg(feature = "std")]
+            runner: OnceCell::new(),
+            #[cfg(not(feature = "std"))]
+            runner: core::cell::UnsafeCell::new(None),
+        }
+    }
+
+    pub const fn name(mut self, name: &'static str) -> Self {
+        self.logger.name = Some(name);
+        self
+    }
+
+    pub const fn level(mut self, level: LevelFilter) -> Self {
+        self.logger = self.logger.with_level(level);
+        self
+    }
+
+    pub const fn on_panic(mut self, strategy: PanicStrategy) -> Self {
+        self.on_panic = strategy;
+        self
+    }
+
+    pub fn update(&'static self) {
+        __begin_tick();
+        #[cfg(feature = "std")]
+        {
+            use std::panic::{catch_unwind, AssertUnwindSafe};
+            let runner = || {
+                self.runner
+                    .get_or_init(|| std::sync::Mutex::new(self.start()))
+                    .lock()
+                    // a panic last tick leaves the lock poisoned, but carrying
+                    // on is exactly what the other strategies are for
+                    .unwrap_or_else(std::sync::PoisonError::into_inner)
+            };
+            let result = catch_unwind(AssertUnwindSafe(|| runner().update()));
+            let streak = match result {
+                Ok(()) => 0,
+                Err(_) => PANIC_STREAK.get() + 1,
+            };
+            PANIC_STREAK.set(streak);
+            match self.on_panic {
+                PanicStrategy::Abort if streak > 0 => std::process::abort(),
+                PanicStrategy::Restart { after } if streak >= after.max(1) as u64 => {
+                    log::warn!("recreating the splitter after {streak} panics in a row");
+                    // if `new` panics too the old splitter is kept for now
+                    if catch_unwind(AssertUnwindSafe(|| *runner() = self.start())).is_ok() {
+                        PANIC_STREAK.set(0);
+                    }
+                }
+                _ => {}
+            }
+        }
+        #[cfg(not(feature = "std"))]
+        unsafe { &mut *self.runner.get() }
+            .get_or_insert_with(|| self.start())
+            .update();
+    }
+
+    fn start(&'static self) -> Runner<S> {
+        init_runtime(&self.logger);
+        Runner::new()
+    }
+}
+
+/// What happens when a splitter panics, which is set with the `on_panic`
+/// option of [`register_autosplitter!`]. Either way the panic is logged first.
+///
+/// Catching panics needs `std`, and also only works when the splitter is
+/// built with `panic = "unwind"`. That isn't the default on wasm, so usually
+/// any panic aborts no matter what this is set to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PanicStrategy {
+    /// Skip the rest of the update and try again next tick.
+    #[default]
+    Continue,
+    /// Stop the splitter for good.
+    Abort,
+    /// Like `Continue`, but once `after` updates in a row have panicked the
+    /// splitter is thrown away and made again with [`Splitter::new`], for
+    /// splitters that can get stuck in a state they never recover from.
+    Restart {
+        /// How many updates in a row have to panic.
+        after: u32,
+    },
+}
+
+/// A registered splitter along with the state it's tracked between updates.
+struct Runner<S> {
+    splitter: S,
+    state: TimerState,
+    process: Option<u64>,
+    clock: TickClock,
+}
+
+impl<S: Splitter> Runner<S> {
+    fn new() -> Self {
+        Self {
+            splitter: S::new(),
+            state: Host.state(),
+            process: None,
+            clock: TickClock::new(),
+        }
+    }
+
+    fn update(&mut self) {
+        self.clock.tick();
+        TICK_DELTA.set(self.clock.delta().as_secs_f64());
+        let state = Host.state();
+        if state != self.state {
+            if let Some(history) = self.splitter.history() {
+                history.record(self.state, state);
+            }
+            self.splitter.on_state_change(self.state, state);
+            match (self.state, state) {
+                (TimerState::NotRunning, TimerState::Running) => self.splitter.on_timer_start(),
+                (_, TimerState::NotRunning) => self.splitter.on_timer_reset(),
+                _ => {}
+            }
+            self.state = state;
+        }
+        // the handle might not be valid anymore, so this can't ask the host
+        let process = Some(LAST_ATTACHED.get()).filter(|&handle| handle != 0);
+        if process != self.process {
+            if self.process.is_some() {
+                self.splitter.on_process_lost();
+            }
+            if process.is_some() {
+                self.splitter.on_process_attached();
+            }
+            self.process = process;
+        }
+        if let Some((state, process)) = self.splitter.watch_state() {
+            state.update(process);
+        }
+        self.splitter.update();
+    }
+}
+
+fn init_runtime(logger: &'static Logger) {
+    init_logger(logger);
+    #[cfg(feature = "std")]
+    std::panic::set_hook(alloc::boxed::Box::new(log_panic));
+}
+
+fn init_logger(logger: &'static Logger) {
+    MAX_LOG_LEVEL.set(logger.level);
+    log::set_logger(logger)
+        .map(|()| log::set_max_level(logger.level))
+        .ok();
+}
+
+#[cfg(feature = "std")]
+fn log_panic(panic_info: &std::panic::PanicHookInfo) {
+    let payload = panic_info.payload();
+    let message = payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| {
+            payload
+                .downcast_ref::<alloc::string::String>()
+                .map(|s| s.as_str())
+        })
+        .unwrap_or("<non-string panic payload>");
+    if let Some(location) = panic_info.location() {
+        log::error!(
+            "panic occurred in file '{}' at line {}: {}",
+            location.file(),
+            location.line(),
+            message,
+        );
+    } else {
+        log::error!(
+            "panic occurred but can't get location information: {}",
+            message
+        );
+    }
 }
 
 /// The main autosplitter trait.
@@ -106,17 +498,95 @@ pub trait Splitter {
     /// Called periodically by the LiveSplit runtime. To change the rate that
     /// it's called, use [`set_tick_rate`](HostFunctions::set_tick_rate)
     fn update(&mut self);
+
+    /// Called right before [`update`](Splitter::update) whenever the timer's
+    /// state is different from what it was on the previous update, for
+    /// example when the runner starts or resets a run. This does nothing
+    /// unless you override it.
+    fn on_state_change(&mut self, old: TimerState, new: TimerState) {
+        let _ = (old, new);
+    }
+
+    /// Where to record the timer's state changes, usually a [`StateHistory`].
+    /// Changes are recorded right before
+    /// [`on_state_change`](Splitter::on_state_change). This does nothing
+    /// unless you override it.
+    fn history(&mut self) -> Option<&mut dyn RecordStates> {
+        None
+    }
+
+    /// The [`StateWatcher`] to update before every [`update`](Splitter::update)
+    /// and the process to read it from, like the `state` block of an ASL
+    /// script. Return `None` while there's no process to read from. This
+    /// does nothing unless you override it.
+    fn watch_state(&mut self) -> Option<(&mut dyn WatchState, &Process)> {
+        None
+    }
+
+    /// Called right before [`update`](Splitter::update) when a run has been
+    /// started, after [`on_state_change`](Splitter::on_state_change). This
+    /// does nothing unless you override it.
+    fn on_timer_start(&mut self) {}
+
+    /// Called right before [`update`](Splitter::update) when a run has been
+    /// reset, whether it was finished or not, after
+    /// [`on_state_change`](Splitter::on_state_change). This does nothing
+    /// unless you override it.
+    fn on_timer_reset(&mut self) {}
+
+    /// Called right before the first [`update`](Splitter::update) after
+    /// attaching to a process. Only the process attached to most recently is
+    /// kept track of, so a splitter that attaches to more than one process
+    /// hears about each of them as it's attached to. This does nothing unless
+    /// you override it.
+    fn on_process_attached(&mut self) {}
+
+    /// Called right before [`update`](Splitter::update) once the process
+    /// [`on_process_attached`](Splitter::on_process_attached) was called for
+    /// has been dropped or [detached](Process::detach), or another process
+    /// was attached to instead. A game exiting doesn't count until its
+    /// [`Process`] is dropped, which [`GameProcess`] does as soon as it
+    /// notices. This does nothing unless you override it.
+    fn on_process_lost(&mut self) {}
 }
 
 /// The autosplitter's interface for interacting with the LiveSpilit timer.
 pub trait HostFunctions {
     /// Attach to a process running on the same machine as the autosplitter.
     fn attach(&self, name: &str) -> Option<Process> {
-        unsafe {
-            match ffi::process_attach(name.as_ptr() as u32, name.len() as u32) {
-                0 => None,
-                n => Some(Process(n)),
+        Process::attach(name)
+    }
+
+    /// Attach to the process with the given PID, which can be found with
+    /// [`list_processes_by_name`](HostFunctions::list_processes_by_name).
+    /// Needs the `process-list` feature.
+    #[cfg(any(test, feature = "process-list"))]
+    fn attach_by_pid(&self, pid: u32) -> Option<Process> {
+        Process::attach_by_pid(pid)
+    }
+
+    /// The PIDs of every process called `name`. When more than one is
+    /// running (like a launcher with the same name as the game)
+    /// [`attach`](HostFunctions::attach) picks any of them, so use this to
+    /// look through them and [`attach_by_pid`](HostFunctions::attach_by_pid)
+    /// to the right one. Needs the `process-list` feature.
+    #[cfg(any(test, feature = "process-list"))]
+    fn list_processes_by_name(&self, name: &str) -> alloc::vec::Vec<u32> {
+        let mut pids = alloc::vec::Vec::<u64>::new();
+        loop {
+            let mut len = pids.capacity();
+            let listed = unsafe {
+                ffi::process_list_by_name(name.as_ptr(), name.len(), pids.as_mut_ptr(), &mut len)
+            };
+            if listed != 0 {
+                // SAFETY: the host wrote `len` PIDs into the buffer
+                unsafe { pids.set_len(len) };
+                return pids.into_iter().map(|pid| pid as u32).collect();
             }
+            if len <= pids.capacity() {
+                return alloc::vec::Vec::new();
+            }
+            pids.reserve_exact(len);
         }
     }
 
@@ -146,6 +616,21 @@ pub trait HostFunctions {
         unsafe { ffi::timer_split() }
     }
 
+    /// Skip the current split, moving on to the next one without a time for
+    /// this segment. Useful when a sequence break means a split's condition
+    /// never happens. Needs the `skip-undo-split` feature.
+    #[cfg(any(test, feature = "skip-undo-split"))]
+    fn skip_split(&self) {
+        unsafe { ffi::timer_skip_split() }
+    }
+
+    /// Undo the last split, for when the splitter split by mistake. Needs the
+    /// `skip-undo-split` feature.
+    #[cfg(any(test, feature = "skip-undo-split"))]
+    fn undo_split(&self) {
+        unsafe { ffi::timer_undo_split() }
+    }
+
     /// Reset the run. Don't do this automatically when a run has finished, and
     /// in general be conservative about resetting runs from the
     /// autosplitter. Common practice is to do so only if there's an
@@ -161,34 +646,185 @@ pub trait HostFunctions {
         unsafe { ffi::timer_set_game_time(time.as_secs() as i64, time.subsec_nanos() as i32) }
     }
 
+    /// Set the game time from a number of seconds, which is how many games
+    /// store their in-game timer. The value is rounded to the nearest
+    /// nanosecond, though keep in mind that an `f64` only has about 15
+    /// significant digits so very long times lose some of that precision
+    /// anyway. Negative times are passed along as-is.
+    fn set_game_time_secs(&self, secs: f64) {
+        // casting truncates towards zero, so this rounds half away from zero
+        let mut whole = secs as i64;
+        let frac = (secs - whole as f64) * 1e9;
+        let mut nanos = if frac < 0.0 { frac - 0.5 } else { frac + 0.5 } as i32;
+        // rounding can carry over into the next whole second
+        if nanos.abs() == 1_000_000_000 {
+            whole += nanos.signum() as i64;
+            nanos = 0;
+        }
+        unsafe { ffi::timer_set_game_time(whole, nanos) }
+    }
+
     /// Set the rate at which the [`update`](Splitter::update) function will be
     /// called (in Hz).
     fn set_tick_rate(&self, rate: f64) {
-        unsafe { ffi::runtime_set_tick_rate(rate) }
+        set_tick_rate(rate);
+    }
+
+    /// Temporarily change the tick rate, for example to make sure a value
+    /// that changes quickly during some section isn't missed. The previous
+    /// rate is restored when the returned guard is dropped.
+    fn tick_rate_scope(&self, rate: f64) -> TickRateGuard {
+        let previous = self.current_tick_rate();
+        set_tick_rate(rate);
+        TickRateGuard { previous }
+    }
+
+    /// Get the tick rate that was last requested with
+    /// [`set_tick_rate`](HostFunctions::set_tick_rate), or
+    /// [`DEFAULT_TICK_RATE`] if it hasn't been changed. The runtime doesn't
+    /// report the rate it's actually running at, so this is only as accurate
+    /// as LiveSplit is at keeping up.
+    fn current_tick_rate(&self) -> f64 {
+        TICK_RATE.get()
+    }
+
+    /// How much time actually passed between the start of the previous update
+    /// and this one, which can be longer than the tick rate suggests if the
+    /// runtime is falling behind. See [`TickClock`] for how this is measured.
+    fn tick_delta(&self) -> Duration {
+        Duration::from_secs_f64(TICK_DELTA.get())
+    }
+
+    /// Turn all logging on or off, for example to keep `log` calls in
+    /// a splitter without them cluttering LiveSplit's log. Messages are
+    /// dropped before they're formatted, so disabled logs are cheap. Logging
+    /// starts out enabled.
+    fn set_logging_enabled(&self, enabled: bool) {
+        LOGGING_ENABLED.set(enabled);
+    }
+
+    /// Only log messages at or above `level`, so `LevelFilter::Debug` shows
+    /// debug messages from your splitter and its dependencies as well. The
+    /// default is `LevelFilter::Info`, or the `level` given to
+    /// [`register_autosplitter!`].
+    fn set_log_level(&self, level: LevelFilter) {
+        MAX_LOG_LEVEL.set(level);
+        log::set_max_level(level);
     }
 
     /// Get the current state of the timer. This is how the autosplitter can
-    /// detect if the player manually paused or reset a run.
+    /// detect if the player manually paused or reset a run. A state this crate
+    /// doesn't know about is treated as [`NotRunning`](TimerState::NotRunning).
     fn state(&self) -> TimerState {
-        unsafe { std::mem::transmute(ffi::timer_get_state() as u8) }
+        match unsafe { ffi::timer_get_state() } {
+            1 => TimerState::Running,
+            2 => TimerState::Paused,
+            3 => TimerState::Ended,
+            _ => TimerState::NotRunning,
+        }
+    }
+
+    /// The index of the split the run is on, counting from 0, or `None` if
+    /// there's no run going. Needs the `split-index` feature.
+    #[cfg(any(test, feature = "split-index"))]
+    fn current_split_index(&self) -> Option<u32> {
+        u32::try_from(unsafe { ffi::timer_current_split_index() }).ok()
     }
 
     /// Set a variable which can be displayed by LiveSplit. This is commonly
     /// used for features like death counters.
     fn set_variable(&self, key: &str, value: &str) {
-        unsafe {
-            ffi::timer_set_variable(
-                key.as_ptr() as u32,
-                key.len() as u32,
-                value.as_ptr() as u32,
-                value.len() as u32,
-            );
+        unsafe { ffi::timer_set_variable(key.as_ptr(), key.len(), value.as_ptr(), value.len()) }
+    }
+
+    /// Show the name of the current objective in the `"objective"` variable,
+    /// which goes well with [`SplitSequence::index`]. Indices past the end
+    /// show the last name, and nothing is set if there are no names.
+    fn set_current_objective(&self, names: &[&str], index: usize) {
+        if let Some(name) = names.get(index).or(names.last()) {
+            self.set_variable("objective", name);
         }
     }
+
+    /// Set a variable to an integer value, formatted without allocating.
+    fn set_variable_int(&self, key: &str, value: i64) {
+        self.set_variable_display(key, value);
+    }
+
+    /// Set a variable to a float rounded to `precision` digits after the
+    /// decimal point.
+    fn set_variable_float(&self, key: &str, value: f64, precision: usize) {
+        self.set_variable_display(key, format_args!("{value:.precision$}"));
+    }
+
+    /// Set a variable to a duration shown the way the timer shows times, as
+    /// `mm:ss.xxx` (or `h:mm:ss.xxx` past an hour).
+    fn set_variable_duration(&self, key: &str, value: Duration) {
+        self.set_variable_display(key, fmt::Clock(value));
+    }
+
+    /// Set a variable to anything that implements [`Display`]. The value is
+    /// formatted into a fixed size buffer rather than a `String` so this is
+    /// cheap to call every tick, but it means values longer than 64 bytes get
+    /// truncated.
+    fn set_variable_display<V: Display>(&self, key: &str, value: V) {
+        let mut buf = fmt::FixedBuf::<64>::new();
+        // running out of space just truncates the value, which is fine here
+        write!(buf, "{value}").ok();
+        self.set_variable(key, buf.as_str());
+    }
 }
 
 impl<T: Splitter> HostFunctions for T {}
 
+/// A standalone handle to the [`HostFunctions`], for when you can't use your
+/// splitter itself. For example helpers stored in your splitter can't take
+/// `self` as their host while they're being mutably borrowed, so you'd pass
+/// `&Host` instead.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Host;
+
+impl HostFunctions for Host {}
+
+fn set_tick_rate(rate: f64) {
+    TICK_RATE.set(rate);
+    unsafe { ffi::runtime_set_tick_rate(rate) }
+}
+
+/// Restores the previous tick rate when dropped. Returned by
+/// [`tick_rate_scope`](HostFunctions::tick_rate_scope).
+#[must_use = "the tick rate is restored as soon as the guard is dropped"]
+#[derive(Debug)]
+pub struct TickRateGuard {
+    previous: f64,
+}
+
+impl Drop for TickRateGuard {
+    fn drop(&mut self) {
+        set_tick_rate(self.previous);
+    }
+}
+
+/// Get a string from a host function that writes it into a buffer. If the
+/// buffer is too small the function fails and sets the length to how much
+/// space is actually needed, so we try again with a buffer that big.
+#[cfg(any(test, feature = "process-path"))]
+fn read_host_string(f: impl Fn(*mut u8, *mut usize) -> u32) -> Option<alloc::string::String> {
+    let mut buf = alloc::vec::Vec::new();
+    loop {
+        let mut len = buf.capacity();
+        if f(buf.as_mut_ptr(), &mut len) != 0 {
+            // SAFETY: the host wrote `len` bytes into the buffer
+            unsafe { buf.set_len(len) };
+            return alloc::string::String::from_utf8(buf).ok();
+        }
+        if len <= buf.capacity() {
+            return None;
+        }
+        buf.reserve_exact(len);
+    }
+}
+
 /// The possible states of the timer.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[repr(u8)]
@@ -203,28 +839,118 @@ pub enum TimerState {
     Ended = 3,
 }
 
+impl TimerState {
+    /// A human readable name for the state, like `"Not Running"`.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            TimerState::NotRunning => "Not Running",
+            TimerState::Running => "Running",
+            TimerState::Paused => "Paused",
+            TimerState::Ended => "Ended",
+        }
+    }
+
+    /// Whether there's a run going, which is when splitters that remove loads
+    /// should be pausing and unpausing game time.
+    pub const fn is_running_or_paused(self) -> bool {
+        matches!(self, TimerState::Running | TimerState::Paused)
+    }
+}
+
+impl Display for TimerState {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The runtime's imports. Buffers are always passed as a pointer and a
+/// `usize` length, never cast to a fixed size integer, so they're as wide as
+/// the module's memory is: 32 bits on `wasm32` and 64 bits on `wasm64`.
+#[cfg(not(any(test, feature = "testing")))]
 mod ffi {
+    // lengths are passed as `usize` next to pointers, so they'd better match
+    const _: () = assert!(core::mem::size_of::<usize>() == core::mem::size_of::<*const u8>());
+
     extern "C" {
         pub(crate) fn runtime_print_message(ptr: *const u8, len: usize);
         pub(crate) fn runtime_set_tick_rate(rate: f64);
-        pub(crate) fn process_attach(ptr: u32, len: u32) -> u64;
+        pub(crate) fn process_attach(ptr: *const u8, len: usize) -> u64;
         pub(crate) fn process_detach(handle: u64);
-        pub(crate) fn process_get_module_address(handle: u64, ptr: u32, len: u32) -> u64;
-        pub(crate) fn process_read(handle: u64, address: u64, buf: u32, buf_len: u32) -> u32;
+        #[cfg(feature = "process-list")]
+        pub(crate) fn process_attach_by_pid(pid: u64) -> u64;
+        #[cfg(feature = "process-list")]
+        pub(crate) fn process_list_by_name(
+            ptr: *const u8,
+            len: usize,
+            list: *mut u64,
+            list_len: *mut usize,
+        ) -> u32;
+        pub(crate) fn process_is_open(handle: u64) -> u32;
+        #[cfg(feature = "process-path")]
+        pub(crate) fn process_get_path(handle: u64, buf: *mut u8, len: *mut usize) -> u32;
+        pub(crate) fn process_get_module_address(handle: u64, ptr: *const u8, len: usize) -> u64;
+        pub(crate) fn process_get_module_size(handle: u64, ptr: *const u8, len: usize) -> u64;
+        pub(crate) fn process_read(handle: u64, address: u64, buf: *mut u8, buf_len: usize) -> u32;
+        #[cfg(feature = "process-write")]
+        pub(crate) fn process_write(
+            handle: u64,
+            address: u64,
+            buf: *const u8,
+            buf_len: usize,
+        ) -> u32;
+        #[cfg(feature = "memory-ranges")]
+        pub(crate) fn process_get_memory_range_count(handle: u64) -> u64;
+        #[cfg(feature = "memory-ranges")]
+        pub(crate) fn process_get_memory_range_address(handle: u64, idx: u64) -> u64;
+        #[cfg(feature = "memory-ranges")]
+        pub(crate) fn process_get_memory_range_size(handle: u64, idx: u64) -> u64;
+        #[cfg(feature = "memory-ranges")]
+        pub(crate) fn process_get_memory_range_flags(handle: u64, idx: u64) -> u64;
         pub(crate) fn timer_start();
         pub(crate) fn timer_split();
         pub(crate) fn timer_reset();
-        pub(crate) fn timer_set_variable(key: u32, key_len: u32, value: u32, value_len: u32);
+        #[cfg(feature = "skip-undo-split")]
+        pub(crate) fn timer_skip_split();
+        #[cfg(feature = "skip-undo-split")]
+        pub(crate) fn timer_undo_split();
+        pub(crate) fn timer_set_variable(
+            key: *const u8,
+            key_len: usize,
+            value: *const u8,
+            value_len: usize,
+        );
         pub(crate) fn timer_set_game_time(seconds: i64, nanos: i32);
         pub(crate) fn timer_pause_game_time();
         pub(crate) fn timer_resume_game_time();
         pub(crate) fn timer_get_state() -> u32;
+        #[cfg(feature = "split-index")]
+        pub(crate) fn timer_current_split_index() -> i32;
+        #[cfg(feature = "settings")]
+        pub(crate) fn user_settings_add_bool(
+            key: *const u8,
+            key_len: usize,
+            description: *const u8,
+            description_len: usize,
+            default: u32,
+        ) -> u32;
+        #[cfg(feature = "settings")]
+        pub(crate) fn settings_map_load() -> u64;
+        #[cfg(feature = "settings")]
+        pub(crate) fn settings_map_get(map: u64, key: *const u8, key_len: usize) -> u64;
+        #[cfg(feature = "settings")]
+        pub(crate) fn setting_value_get_bool(value: u64, out: *mut u32) -> u32;
+        #[cfg(feature = "settings")]
+        pub(crate) fn settings_map_free(map: u64);
+        #[cfg(feature = "settings")]
+        pub(crate) fn setting_value_free(value: u64);
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use alloc::format;
+    use alloc::vec::Vec;
 
     #[derive(Debug, Default, Clone, Copy)]
     struct Unit;
@@ -240,4 +966,417 @@ mod test {
     }
 
     register_autosplitter!(Unit);
+
+    #[test]
+    fn attach_by_pid() {
+        mock::with_host(|h| {
+            h.spawn(1, "Game.exe");
+            h.spawn(2, "Launcher.exe");
+            h.spawn(3, "Game.exe").path = Some("C:\\Games\\Game\\Game.exe".into());
+        });
+        let pids = Unit.list_processes_by_name("Game.exe");
+        assert_eq!(pids, [1001, 1003]);
+        assert_eq!(Unit.list_processes_by_name("Other.exe"), []);
+        let process = Unit.attach_by_pid(pids[1]).unwrap();
+        assert_eq!(process.path().as_deref(), Some("C:\\Games\\Game\\Game.exe"));
+        assert_eq!(process.name(), "Game.exe");
+        assert!(Unit.attach_by_pid(1).is_none());
+    }
+
+    #[test]
+    fn attach_read_split() {
+        mock::with_host(|h| h.spawn(1, "Game.exe").map(0x1000, 5u32.to_le_bytes()));
+        assert!(Unit.attach("Other.exe").is_none());
+        let process = Unit.attach("Game.exe").unwrap();
+        assert_eq!(Unit.state(), TimerState::NotRunning);
+        Unit.start();
+        assert_eq!(Unit.state(), TimerState::Running);
+        if process.read::<u32>(0x1000).unwrap() == 5 {
+            Unit.split();
+        }
+        drop(process);
+        mock::with_host(|h| {
+            assert_eq!(h.splits, 1);
+            assert_eq!(h.detaches, [1]);
+        });
+    }
+
+    #[test]
+    fn skip_and_undo() {
+        Unit.undo_split();
+        Unit.start();
+        Unit.skip_split();
+        Unit.split();
+        Unit.undo_split();
+        mock::with_host(|h| {
+            assert_eq!(h.splits, 1);
+            assert_eq!(h.skips, 1);
+        });
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn panic_message_is_logged() {
+        static LOGGER: Logger = Logger::new(None);
+        init_runtime(&LOGGER);
+        let addr = 0x1234;
+        std::panic::catch_unwind(|| panic!("bad read at {addr:#x}")).unwrap_err();
+        std::panic::catch_unwind(|| std::panic::panic_any(42)).unwrap_err();
+        // put the default hook back so other tests' failures still get printed
+        drop(std::panic::take_hook());
+        mock::with_host(|h| {
+            assert!(h.log[0].ends_with(": bad read at 0x1234"), "{}", h.log[0]);
+            assert!(
+                h.log[1].ends_with(": <non-string panic payload>"),
+                "{}",
+                h.log[1]
+            );
+        });
+    }
+
+    #[test]
+    fn log_prefix() {
+        use log::Log;
+        let log = |logger: Logger| {
+            logger.log(
+                &Record::builder()
+                    .level(Level::Warn)
+                    .target("celeste::levels")
+                    .args(format_args!("can't find the level"))
+                    .build(),
+            );
+            mock::with_host(|h| h.log.pop().unwrap())
+        };
+        assert_eq!(
+            log(Logger::new(None)),
+            "⚠️ celeste::levels: can't find the level"
+        );
+        assert_eq!(
+            log(Logger::new(Some("Celeste"))),
+            "⚠️ [Celeste] celeste::levels: can't find the level"
+        );
+    }
+
+    #[test]
+    fn log_levels() {
+        use log::Log;
+        let log = |level| {
+            Logger::new(None).log(
+                &Record::builder()
+                    .level(level)
+                    .target("splitter")
+                    .args(format_args!("entered level"))
+                    .key_values(&[("level", 3)])
+                    .build(),
+            );
+        };
+        log(Level::Debug);
+        Unit.set_log_level(LevelFilter::Debug);
+        log(Level::Debug);
+        log(Level::Trace);
+        mock::with_host(|h| assert_eq!(h.log, ["🐞 splitter: entered level level=3"]));
+    }
+
+    #[test]
+    fn log_level_from_registration() {
+        use log::Log;
+        static REGISTRATION: __Registration<Unit> = __Registration::new()
+            .name("Celeste")
+            .level(LevelFilter::Trace);
+        let logger = &REGISTRATION.logger;
+        assert!(!logger.enabled(&Metadata::builder().level(Level::Trace).build()));
+        init_runtime(logger);
+        #[cfg(feature = "std")]
+        drop(std::panic::take_hook());
+        assert!(logger.enabled(&Metadata::builder().level(Level::Trace).build()));
+        assert_eq!(logger.name, Some("Celeste"));
+    }
+
+    #[test]
+    fn long_logs_are_truncated() {
+        let long = "a".repeat(2000);
+        log::Log::log(
+            &Logger::new(None),
+            &Record::builder()
+                .level(Level::Info)
+                .target("splitter")
+                .args(format_args!("{long}"))
+                .build(),
+        );
+        let logged = mock::with_host(|h| h.log.pop().unwrap());
+        assert_eq!(logged.len(), LOG_BUFFER_SIZE);
+        assert!(logged.starts_with("splitter: aaa"));
+        assert!(logged.ends_with("a…"));
+    }
+
+    #[test]
+    fn logging_switch() {
+        use log::Log;
+        let log = |message| {
+            Logger::new(None).log(
+                &Record::builder()
+                    .level(Level::Info)
+                    .target("splitter")
+                    .args(format_args!("{message}"))
+                    .build(),
+            );
+        };
+        log("one");
+        Unit.set_logging_enabled(false);
+        log("two");
+        Unit.set_logging_enabled(true);
+        log("three");
+        mock::with_host(|h| assert_eq!(h.log, ["splitter: one", "splitter: three"]));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn panicking_update_is_caught() {
+        struct Flaky(u32);
+        impl Splitter for Flaky {
+            fn new() -> Self {
+                Flaky(0)
+            }
+
+            fn update(&mut self) {
+                self.0 += 1;
+                if self.0 == 1 {
+                    panic!("first update");
+                }
+                self.set_variable_int("updates", self.0.into());
+            }
+        }
+
+        static REGISTRATION: __Registration<Flaky> = __Registration::new();
+        REGISTRATION.update();
+        REGISTRATION.update();
+        drop(std::panic::take_hook());
+        mock::with_host(|h| {
+            assert!(h.log.iter().any(|l| l.ends_with(": first update")));
+            assert_eq!(h.variables, [("updates".into(), "2".into())]);
+        });
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn panicking_splitter_is_restarted() {
+        struct Stuck(u32);
+        impl Splitter for Stuck {
+            fn new() -> Self {
+                Unit.set_variable("created", "");
+                Stuck(0)
+            }
+
+            fn update(&mut self) {
+                self.0 += 1;
+                if self.0 > 1 {
+                    panic!("stuck");
+                }
+            }
+        }
+
+        static REGISTRATION: __Registration<Stuck> =
+            __Registration::new().on_panic(PanicStrategy::Restart { after: 2 });
+        for _ in 0..4 {
+            REGISTRATION.update();
+        }
+        drop(std::panic::take_hook());
+        mock::with_host(|h| {
+            let created = h.variables.iter().filter(|(k, _)| k == "created").count();
+            assert_eq!(created, 2);
+            assert!(h.log.iter().any(|l| l.ends_with("after 2 panics in a row")));
+        });
+    }
+
+    #[test]
+    fn state_change_callback() {
+        struct Recorder;
+        impl Splitter for Recorder {
+            fn new() -> Self {
+                Recorder
+            }
+
+            fn update(&mut self) {}
+
+            fn on_state_change(&mut self, old: TimerState, new: TimerState) {
+                self.set_variable("transition", &format!("{old:?} -> {new:?}"));
+            }
+        }
+
+        static REGISTRATION: __Registration<Recorder> = __Registration::new();
+        let registration = &REGISTRATION;
+        registration.update();
+        // the first update installs the panic hook, so put the default back
+        #[cfg(feature = "std")]
+        drop(std::panic::take_hook());
+        Unit.start();
+        registration.update();
+        registration.update();
+        mock::with_host(|h| h.state = TimerState::Paused as u32);
+        registration.update();
+        Unit.reset();
+        registration.update();
+        mock::with_host(|h| {
+            let transitions: Vec<_> = h.variables.iter().map(|(_, v)| v.as_str()).collect();
+            assert_eq!(
+                transitions,
+                [
+                    "NotRunning -> Running",
+                    "Running -> Paused",
+                    "Paused -> NotRunning"
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn lifecycle_hooks() {
+        #[derive(Default)]
+        struct Recorder(Option<Process>);
+        impl Splitter for Recorder {
+            fn new() -> Self {
+                Recorder::default()
+            }
+
+            fn update(&mut self) {
+                if self.0.as_ref().is_some_and(|p| !p.is_open()) {
+                    self.0 = None;
+                } else if self.0.is_none() {
+                    self.0 = self.attach("Game.exe");
+                }
+            }
+
+            fn on_timer_start(&mut self) {
+                self.set_variable("hook", "start");
+            }
+
+            fn on_timer_reset(&mut self) {
+                self.set_variable("hook", "reset");
+            }
+
+            fn on_process_attached(&mut self) {
+                self.set_variable("hook", "attached");
+            }
+
+            fn on_process_lost(&mut self) {
+                self.set_variable("hook", "lost");
+            }
+        }
+
+        static REGISTRATION: __Registration<Recorder> = __Registration::new();
+        let registration = &REGISTRATION;
+        registration.update();
+        #[cfg(feature = "std")]
+        drop(std::panic::take_hook());
+        mock::with_host(|h| {
+            h.spawn(1, "Game.exe");
+        });
+        // attaches during this update, so the hook runs before the next one
+        registration.update();
+        registration.update();
+        Unit.start();
+        registration.update();
+        mock::with_host(|h| h.state = TimerState::Ended as u32);
+        registration.update();
+        Unit.reset();
+        registration.update();
+        mock::with_host(|h| h.process(1).closed = true);
+        registration.update();
+        registration.update();
+        mock::with_host(|h| {
+            let hooks: Vec<_> = h.variables.iter().map(|(_, v)| v.as_str()).collect();
+            assert_eq!(hooks, ["attached", "start", "reset", "lost"]);
+        });
+    }
+
+    #[test]
+    fn set_variable_formatting() {
+        Unit.set_variable_int("deaths", -42);
+        Unit.set_variable_display("speed", 1.5);
+        Unit.set_variable_display("long", "x".repeat(100));
+        mock::with_host(|h| {
+            assert_eq!(h.variables[0], ("deaths".into(), "-42".into()));
+            assert_eq!(h.variables[1], ("speed".into(), "1.5".into()));
+            assert_eq!(h.variables[2], ("long".into(), "x".repeat(64)));
+        });
+        Unit.set_variable_float("speed", 2.0 / 3.0, 2);
+        Unit.set_variable_duration("igt", Duration::from_millis(83_500));
+        mock::with_host(|h| {
+            assert_eq!(h.variables[3], ("speed".into(), "0.67".into()));
+            assert_eq!(h.variables[4], ("igt".into(), "01:23.500".into()));
+        });
+    }
+
+    #[test]
+    fn current_objective() {
+        let names = ["Prologue", "Forsaken City"];
+        Unit.set_current_objective(&names, 0);
+        Unit.set_current_objective(&names, 1);
+        Unit.set_current_objective(&names, 5);
+        Unit.set_current_objective(&[], 0);
+        mock::with_host(|h| {
+            let values: Vec<_> = h.variables.iter().map(|(_, v)| v.as_str()).collect();
+            assert_eq!(values, ["Prologue", "Forsaken City", "Forsaken City"]);
+            assert!(h.variables.iter().all(|(k, _)| k == "objective"));
+        });
+    }
+
+    #[test]
+    fn tick_rate() {
+        assert_eq!(Unit.current_tick_rate(), DEFAULT_TICK_RATE);
+        Unit.set_tick_rate(60.0);
+        assert_eq!(Unit.current_tick_rate(), 60.0);
+        mock::with_host(|h| assert_eq!(h.tick_rate, Some(60.0)));
+    }
+
+    #[test]
+    fn tick_rate_scope() {
+        Unit.set_tick_rate(30.0);
+        let guard = Unit.tick_rate_scope(240.0);
+        assert_eq!(Unit.current_tick_rate(), 240.0);
+        drop(guard);
+        assert_eq!(Unit.current_tick_rate(), 30.0);
+        mock::with_host(|h| assert_eq!(h.tick_rate, Some(30.0)));
+    }
+
+    #[test]
+    fn timer_state_display() {
+        use TimerState::*;
+        let names = [NotRunning, Running, Paused, Ended].map(|s| format!("{s}"));
+        assert_eq!(names, ["Not Running", "Running", "Paused", "Ended"]);
+        assert_eq!(Paused.as_str(), "Paused");
+        assert!(Running.is_running_or_paused() && Paused.is_running_or_paused());
+        assert!(!NotRunning.is_running_or_paused() && !Ended.is_running_or_paused());
+    }
+
+    #[test]
+    fn unknown_timer_state() {
+        mock::with_host(|h| h.state = TimerState::Paused as u32);
+        assert_eq!(Unit.state(), TimerState::Paused);
+        // a newer runtime could have more states
+        mock::with_host(|h| h.state = 7);
+        assert_eq!(Unit.state(), TimerState::NotRunning);
+    }
+
+    #[test]
+    fn current_split_index() {
+        assert_eq!(Unit.current_split_index(), None);
+        Unit.start();
+        assert_eq!(Unit.current_split_index(), Some(0));
+        Unit.split();
+        assert_eq!(Unit.current_split_index(), Some(1));
+    }
+
+    #[test]
+    fn game_time_from_secs() {
+        let game_time = |secs| {
+            Unit.set_game_time_secs(secs);
+            mock::with_host(|h| h.game_time.unwrap())
+        };
+        assert_eq!(game_time(0.0), (0, 0));
+        assert_eq!(game_time(42.0), (42, 0));
+        assert_eq!(game_time(1.25), (1, 250_000_000));
+        assert_eq!(game_time(-1.5), (-1, -500_000_000));
+        assert_eq!(game_time(2.9999999999), (3, 0));
+    }
 }
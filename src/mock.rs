@@ -0,0 +1,521 @@
+//! Native stand-ins for the runtime's host functions so tests can exercise the
+//! real code paths instead of linking against imports that only exist in wasm.
+//! Every test thread gets its own fresh [`Host`], so tests set up whatever
+//! processes they need with [`with_host`], call into the crate as usual, and
+//! then check what the host saw.
+
+// the `testing` module only exposes part of what the crate's own tests use
+#![cfg_attr(not(test), allow(dead_code))]
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::prelude::rust_2021::*;
+
+/// The in-memory state that the mocked host functions read and write.
+#[derive(Debug, Default)]
+pub(crate) struct Host {
+    pub(crate) log: Vec<String>,
+    pub(crate) tick_rate: Option<f64>,
+    pub(crate) state: u32,
+    pub(crate) splits: u32,
+    pub(crate) resets: u32,
+    pub(crate) skips: u32,
+    pub(crate) game_time: Option<(i64, i32)>,
+    pub(crate) game_time_sets: u32,
+    pub(crate) game_time_paused: bool,
+    pub(crate) pauses: u32,
+    pub(crate) resumes: u32,
+    pub(crate) variables: Vec<(String, String)>,
+    /// Fake processes by their ID, which is what tests refer to them by.
+    pub(crate) processes: HashMap<u64, MockProcess>,
+    /// The ID of the process each open handle was attached to.
+    pub(crate) handles: HashMap<u64, u64>,
+    pub(crate) next_handle: u64,
+    pub(crate) attaches: u32,
+    pub(crate) detaches: Vec<u64>,
+    pub(crate) module_lookups: u32,
+    pub(crate) reads: u32,
+    pub(crate) settings: Vec<(String, String, bool)>,
+    pub(crate) user_settings: HashMap<String, bool>,
+    pub(crate) settings_handles: HashMap<u64, MockSetting>,
+    pub(crate) next_settings_handle: u64,
+}
+
+impl Host {
+    /// Get the process with the given ID, creating it if needed.
+    pub(crate) fn process(&mut self, id: u64) -> &mut MockProcess {
+        self.processes.entry(id).or_default()
+    }
+
+    /// Start a new process that can be attached to by name.
+    pub(crate) fn spawn(&mut self, id: u64, name: &str) -> &mut MockProcess {
+        let p = self.process(id);
+        p.name = name.to_owned();
+        p
+    }
+
+    /// Hand out a new handle for the process with the given ID. Like the real
+    /// runtime every attach gets its own handle, even to the same process.
+    pub(crate) fn open(&mut self, id: u64) -> u64 {
+        self.next_handle += 1;
+        self.handles.insert(self.next_handle, id);
+        self.next_handle
+    }
+
+    /// The process a handle was attached to. The real runtime traps on
+    /// handles that were never handed out or were already detached, so this
+    /// panics for those.
+    fn attached(&mut self, handle: u64) -> &mut MockProcess {
+        let Some(id) = self.handles.get(&handle) else {
+            unreachable!("invalid process handle {handle}")
+        };
+        self.processes.get_mut(id).unwrap()
+    }
+}
+
+/// Something a settings handle points to. Handles are removed when they're
+/// freed, so tests can check that none leak.
+#[derive(Debug)]
+pub(crate) enum MockSetting {
+    Map(HashMap<String, bool>),
+    Bool(bool),
+}
+
+impl Host {
+    fn new_settings_handle(&mut self, setting: MockSetting) -> u64 {
+        self.next_settings_handle += 1;
+        self.settings_handles
+            .insert(self.next_settings_handle, setting);
+        self.next_settings_handle
+    }
+}
+
+/// A fake process made up of disjoint regions of readable memory.
+#[derive(Debug, Default)]
+pub(crate) struct MockProcess {
+    pub(crate) name: String,
+    pub(crate) closed: bool,
+    pub(crate) path: Option<String>,
+    pub(crate) modules: Vec<(String, u64, u64)>,
+    pub(crate) memory: Vec<(u64, Vec<u8>)>,
+    /// Protection flags for mapped regions that aren't just read and write.
+    pub(crate) protection: HashMap<u64, u64>,
+}
+
+impl MockProcess {
+    /// Make `bytes` readable starting at `addr`.
+    pub(crate) fn map(&mut self, addr: u64, bytes: impl Into<Vec<u8>>) {
+        self.memory.push((addr, bytes.into()));
+    }
+
+    /// Change the protection flags of the region mapped at `base`.
+    pub(crate) fn protect(&mut self, base: u64, flags: u64) {
+        self.protection.insert(base, flags);
+    }
+
+    /// Overwrite mapped memory starting at `addr`, which is how tests
+    /// simulate the game changing a value.
+    pub(crate) fn write(&mut self, addr: u64, bytes: &[u8]) {
+        let (base, region) = self
+            .memory
+            .iter_mut()
+            .find(|(base, region)| (*base..*base + region.len() as u64).contains(&addr))
+            .expect("writing to unmapped memory");
+        let start = (addr - *base) as usize;
+        region[start..start + bytes.len()].copy_from_slice(bytes);
+    }
+
+    /// Load a module called `name` at `base`, with `bytes` as its contents.
+    pub(crate) fn load(&mut self, name: &str, base: u64, bytes: impl Into<Vec<u8>>) {
+        let bytes = bytes.into();
+        self.modules
+            .push((name.to_owned(), base, bytes.len() as u64));
+        self.map(base, bytes);
+    }
+
+    fn module(&self, ptr: *const u8, len: usize) -> Option<&(String, u64, u64)> {
+        let name = unsafe { str_from(ptr, len) };
+        self.modules.iter().find(|(n, ..)| n == name)
+    }
+
+    /// Overwrite memory the way the game writing it would, failing if any of
+    /// it isn't mapped or writable.
+    fn write_checked(&mut self, addr: u64, bytes: &[u8]) -> bool {
+        if self.closed {
+            return false;
+        }
+        let Some((base, region)) = self
+            .memory
+            .iter_mut()
+            .find(|(base, region)| (*base..*base + region.len() as u64).contains(&addr))
+        else {
+            return false;
+        };
+        let start = (addr - *base) as usize;
+        let writable = self
+            .protection
+            .get(base)
+            .is_none_or(|flags| flags & 0b100 != 0);
+        match region.get_mut(start..start + bytes.len()) {
+            Some(dest) if writable => {
+                dest.copy_from_slice(bytes);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn read(&self, addr: u64, len: usize) -> Option<&[u8]> {
+        if self.closed {
+            return None;
+        }
+        self.memory.iter().find_map(|(base, bytes)| {
+            let start = usize::try_from(addr.checked_sub(*base)?).ok()?;
+            bytes.get(start..start.checked_add(len)?)
+        })
+    }
+}
+
+/// Bytes for a fake module or heap, where values can be put at any offset
+/// before it's mapped into a [`MockProcess`].
+pub(crate) struct Memory(pub(crate) Vec<u8>);
+
+impl Memory {
+    /// `len` bytes of zeros.
+    pub(crate) fn new(len: usize) -> Self {
+        Self(vec![0; len])
+    }
+
+    pub(crate) fn put(&mut self, offset: u64, bytes: &[u8]) -> &mut Self {
+        let offset = offset as usize;
+        self.0[offset..offset + bytes.len()].copy_from_slice(bytes);
+        self
+    }
+
+    /// Put a 64-bit pointer at `offset`.
+    pub(crate) fn ptr(&mut self, offset: u64, addr: u64) -> &mut Self {
+        self.put(offset, &addr.to_le_bytes())
+    }
+
+    /// Make this a PE32+ module that exports only `symbol`, at `rva` from the
+    /// start of the module. The headers take up the first 0x300 bytes.
+    pub(crate) fn pe_export(&mut self, symbol: &str, rva: u32) -> &mut Self {
+        self.put(0x3C, &0x80u32.to_le_bytes())
+            .put(0x98, &0x20Bu16.to_le_bytes())
+            // the export directory
+            .put(0x108, &0x200u32.to_le_bytes())
+            .put(0x218, &1u32.to_le_bytes())
+            .put(0x21C, &0x240u32.to_le_bytes())
+            .put(0x220, &0x250u32.to_le_bytes())
+            .put(0x224, &0x260u32.to_le_bytes())
+            .put(0x240, &rva.to_le_bytes())
+            .put(0x250, &0x270u32.to_le_bytes())
+            .put(0x270, symbol.as_bytes())
+    }
+}
+
+thread_local! {
+    static HOST: RefCell<Host> = RefCell::default();
+}
+
+/// Run `f` with access to the current thread's mocked host.
+pub(crate) fn with_host<R>(f: impl FnOnce(&mut Host) -> R) -> R {
+    HOST.with(|h| f(&mut h.borrow_mut()))
+}
+
+unsafe fn str_from<'a>(ptr: *const u8, len: usize) -> &'a str {
+    std::str::from_utf8(std::slice::from_raw_parts(ptr, len)).unwrap()
+}
+
+pub(crate) unsafe fn runtime_print_message(ptr: *const u8, len: usize) {
+    let msg = str_from(ptr, len).to_owned();
+    with_host(|h| h.log.push(msg));
+}
+
+pub(crate) unsafe fn runtime_set_tick_rate(rate: f64) {
+    with_host(|h| h.tick_rate = Some(rate));
+}
+
+/// Copy `s` into the buffer if it fits, otherwise fail and report the needed
+/// length like the real host does.
+unsafe fn write_string(s: Option<&str>, buf: *mut u8, len: *mut usize) -> u32 {
+    let Some(s) = s else { return 0 };
+    let fits = s.len() <= *len;
+    if fits {
+        std::ptr::copy_nonoverlapping(s.as_ptr(), buf, s.len());
+    }
+    *len = s.len();
+    fits as u32
+}
+
+pub(crate) unsafe fn process_attach(ptr: *const u8, len: usize) -> u64 {
+    let name = str_from(ptr, len);
+    with_host(|h| {
+        let id = h
+            .processes
+            .iter()
+            .filter(|(_, p)| !p.closed && p.name == name)
+            .map(|(&id, _)| id)
+            .min();
+        h.attaches += id.is_some() as u32;
+        id.map_or(0, |id| h.open(id))
+    })
+}
+
+/// Fake processes get a PID this far from their ID, so mixing the two up
+/// breaks tests.
+pub(crate) const PID_OFFSET: u64 = 1000;
+
+pub(crate) unsafe fn process_attach_by_pid(pid: u64) -> u64 {
+    with_host(|h| {
+        let id = pid.wrapping_sub(PID_OFFSET);
+        let open = h.processes.get(&id).is_some_and(|p| !p.closed);
+        h.attaches += open as u32;
+        if open {
+            h.open(id)
+        } else {
+            0
+        }
+    })
+}
+
+pub(crate) unsafe fn process_list_by_name(
+    ptr: *const u8,
+    len: usize,
+    list: *mut u64,
+    list_len: *mut usize,
+) -> u32 {
+    let name = str_from(ptr, len);
+    let mut pids: Vec<u64> = with_host(|h| {
+        h.processes
+            .iter()
+            .filter(|(_, p)| !p.closed && p.name == name)
+            .map(|(&id, _)| id + PID_OFFSET)
+            .collect()
+    });
+    pids.sort_unstable();
+    let fits = pids.len() <= *list_len;
+    if fits {
+        std::ptr::copy_nonoverlapping(pids.as_ptr(), list, pids.len());
+    }
+    *list_len = pids.len();
+    fits as u32
+}
+
+pub(crate) unsafe fn process_detach(handle: u64) {
+    with_host(|h| {
+        if h.handles.remove(&handle).is_none() {
+            unreachable!("invalid process handle {handle}");
+        }
+        h.detaches.push(handle);
+    });
+}
+
+pub(crate) unsafe fn process_is_open(handle: u64) -> u32 {
+    with_host(|h| !h.attached(handle).closed as u32)
+}
+
+pub(crate) unsafe fn process_get_module_address(handle: u64, ptr: *const u8, len: usize) -> u64 {
+    with_host(|h| {
+        h.module_lookups += 1;
+        h.attached(handle).module(ptr, len).map_or(0, |m| m.1)
+    })
+}
+
+pub(crate) unsafe fn process_get_module_size(handle: u64, ptr: *const u8, len: usize) -> u64 {
+    with_host(|h| h.attached(handle).module(ptr, len).map_or(0, |m| m.2))
+}
+
+pub(crate) unsafe fn process_read(handle: u64, address: u64, buf: *mut u8, buf_len: usize) -> u32 {
+    let buf = std::slice::from_raw_parts_mut(buf, buf_len);
+    with_host(|h| {
+        h.reads += 1;
+        match h.attached(handle).read(address, buf_len) {
+            Some(bytes) => {
+                buf.copy_from_slice(bytes);
+                1
+            }
+            None => 0,
+        }
+    })
+}
+
+pub(crate) unsafe fn process_write(
+    handle: u64,
+    address: u64,
+    buf: *const u8,
+    buf_len: usize,
+) -> u32 {
+    let buf = std::slice::from_raw_parts(buf, buf_len);
+    with_host(|h| h.attached(handle).write_checked(address, buf) as u32)
+}
+
+pub(crate) unsafe fn process_get_memory_range_count(handle: u64) -> u64 {
+    with_host(|h| h.attached(handle).memory.len() as u64)
+}
+
+pub(crate) unsafe fn process_get_memory_range_address(handle: u64, idx: u64) -> u64 {
+    with_host(|h| h.attached(handle).memory[idx as usize].0)
+}
+
+pub(crate) unsafe fn process_get_memory_range_size(handle: u64, idx: u64) -> u64 {
+    with_host(|h| h.attached(handle).memory[idx as usize].1.len() as u64)
+}
+
+pub(crate) unsafe fn process_get_memory_range_flags(handle: u64, idx: u64) -> u64 {
+    with_host(|h| {
+        let p = h.attached(handle);
+        let base = p.memory[idx as usize].0;
+        // bit 0 is always set so the runtime can tell flags apart from failure
+        1 | p.protection.get(&base).copied().unwrap_or(0b110)
+    })
+}
+
+pub(crate) unsafe fn process_get_path(handle: u64, buf: *mut u8, len: *mut usize) -> u32 {
+    with_host(|h| write_string(h.attached(handle).path.as_deref(), buf, len))
+}
+
+pub(crate) unsafe fn timer_start() {
+    with_host(|h| {
+        if h.state == 0 {
+            h.state = 1
+        }
+    });
+}
+
+pub(crate) unsafe fn timer_split() {
+    with_host(|h| {
+        if h.state == 1 || h.state == 2 {
+            h.splits += 1
+        }
+    });
+}
+
+pub(crate) unsafe fn timer_skip_split() {
+    with_host(|h| {
+        if h.state == 1 || h.state == 2 {
+            h.splits += 1;
+            h.skips += 1;
+        }
+    });
+}
+
+pub(crate) unsafe fn timer_undo_split() {
+    with_host(|h| {
+        if h.state != 0 && h.splits > 0 {
+            h.splits -= 1;
+            if h.state == 3 {
+                h.state = 1;
+            }
+        }
+    });
+}
+
+pub(crate) unsafe fn timer_reset() {
+    with_host(|h| {
+        h.state = 0;
+        h.splits = 0;
+        h.resets += 1;
+    });
+}
+
+pub(crate) unsafe fn timer_set_variable(
+    key: *const u8,
+    key_len: usize,
+    value: *const u8,
+    value_len: usize,
+) {
+    let var = (
+        str_from(key, key_len).to_owned(),
+        str_from(value, value_len).to_owned(),
+    );
+    with_host(|h| h.variables.push(var));
+}
+
+pub(crate) unsafe fn timer_set_game_time(seconds: i64, nanos: i32) {
+    with_host(|h| {
+        h.game_time = Some((seconds, nanos));
+        h.game_time_sets += 1;
+    });
+}
+
+pub(crate) unsafe fn timer_pause_game_time() {
+    with_host(|h| {
+        h.game_time_paused = true;
+        h.pauses += 1;
+    });
+}
+
+pub(crate) unsafe fn timer_resume_game_time() {
+    with_host(|h| {
+        h.game_time_paused = false;
+        h.resumes += 1;
+    });
+}
+
+pub(crate) unsafe fn timer_current_split_index() -> i32 {
+    with_host(|h| if h.state == 0 { -1 } else { h.splits as i32 })
+}
+
+pub(crate) unsafe fn timer_get_state() -> u32 {
+    with_host(|h| h.state)
+}
+
+pub(crate) unsafe fn user_settings_add_bool(
+    key_ptr: *const u8,
+    key_len: usize,
+    desc_ptr: *const u8,
+    desc_len: usize,
+    default: u32,
+) -> u32 {
+    let key = str_from(key_ptr, key_len).to_owned();
+    let desc = str_from(desc_ptr, desc_len).to_owned();
+    with_host(|h| {
+        let value = h.user_settings.get(&key).copied().unwrap_or(default != 0);
+        h.settings.push((key, desc, default != 0));
+        value as u32
+    })
+}
+
+pub(crate) unsafe fn settings_map_load() -> u64 {
+    with_host(|h| {
+        let map = h
+            .settings
+            .iter()
+            .map(|(key, _, default)| {
+                let value = h.user_settings.get(key).copied().unwrap_or(*default);
+                (key.clone(), value)
+            })
+            .collect();
+        h.new_settings_handle(MockSetting::Map(map))
+    })
+}
+
+pub(crate) unsafe fn settings_map_get(map: u64, key_ptr: *const u8, key_len: usize) -> u64 {
+    let key = str_from(key_ptr, key_len);
+    with_host(|h| {
+        let value = match h.settings_handles.get(&map) {
+            Some(MockSetting::Map(map)) => map.get(key).copied(),
+            _ => unreachable!("invalid settings map handle"),
+        };
+        value.map_or(0, |value| h.new_settings_handle(MockSetting::Bool(value)))
+    })
+}
+
+pub(crate) unsafe fn setting_value_get_bool(value: u64, out: *mut u32) -> u32 {
+    with_host(|h| match h.settings_handles.get(&value) {
+        Some(&MockSetting::Bool(value)) => {
+            *out = value as u32;
+            1
+        }
+        _ => 0,
+    })
+}
+
+pub(crate) unsafe fn settings_map_free(map: u64) {
+    with_host(|h| h.settings_handles.remove(&map));
+}
+
+pub(crate) unsafe fn setting_value_free(value: u64) {
+    with_host(|h| h.settings_handles.remove(&value));
+}
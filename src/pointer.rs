@@ -0,0 +1,269 @@
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::any::TypeId;
+use core::cell::Cell;
+use core::marker::PhantomData;
+use core::mem;
+
+use crate::{Address, Error, Pod, Process, Result};
+
+/// A path to a value through a chain of pointers, starting from a fixed
+/// address or an offset into a module. This is the same thing as
+/// [`read_at_offsets`](Process::read_at_offsets), except the offsets are
+/// kept together with the type of the value at the end.
+///
+/// The path is only followed on the first read, after which the address it
+/// led to is reused. If a read fails the path is followed again right away,
+/// so a value that moved somewhere else is found again as long as the old
+/// address stopped being readable. For values that can move without that
+/// happening, call [`invalidate`](DeepPointer::invalidate) when they might
+/// have (like on a level load).
+///
+/// ```no_run
+/// # use livesplit_wrapper::{DeepPointer, Process};
+/// # fn f(process: &Process) {
+/// let level = DeepPointer::<u32>::from_module("UnityPlayer.dll", 0x1A2B3C, &[0x10, 0x48]);
+/// if level.read(process) == Ok(3) {
+///     log::info!("made it to level 3");
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct DeepPointer<T> {
+    base: Base,
+    offsets: Vec<i64>,
+    resolved: Cell<Option<Address>>,
+    _type: PhantomData<fn() -> T>,
+}
+
+#[derive(Debug, Clone)]
+enum Base {
+    Address(Address),
+    Module(String, i64),
+}
+
+impl Base {
+    fn address(&self, process: &Process) -> Result<Address> {
+        match self {
+            Base::Address(addr) => Ok(*addr),
+            Base::Module(name, offset) => process
+                .module(name)
+                .map(|base| base.wrapping_add_signed(*offset))
+                .ok_or_else(|| Error::ModuleNotFound(name.clone())),
+        }
+    }
+}
+
+impl<T: Pod> DeepPointer<T> {
+    /// Create a path that starts at `base` and follows `offsets`.
+    pub fn new(base: Address, offsets: &[i64]) -> Self {
+        Self {
+            base: Base::Address(base),
+            offsets: offsets.to_vec(),
+            resolved: Cell::new(None),
+            _type: PhantomData,
+        }
+    }
+
+    /// Create a path that starts `offset` bytes into `module` and follows
+    /// `offsets`. The module is looked up when the path is followed, so it's
+    /// fine to create this before the game has loaded it.
+    pub fn from_module(module: &str, offset: i64, offsets: &[i64]) -> Self {
+        Self {
+            base: Base::Module(module.into(), offset),
+            ..Self::new(0, offsets)
+        }
+    }
+
+    /// The address of the value, following the path if it hasn't been
+    /// followed since the last [`invalidate`](DeepPointer::invalidate). If
+    /// the path starts in a module that isn't loaded this is an
+    /// [`Error::ModuleNotFound`].
+    pub fn resolve(&self, process: &Process) -> Result<Address> {
+        if let Some(addr) = self.resolved.get() {
+            return Ok(addr);
+        }
+        let addr = process.resolve_offsets(self.base.address(process)?, &self.offsets)?;
+        self.resolved.set(Some(addr));
+        Ok(addr)
+    }
+
+    /// Read the value at the end of the path. If this fails the path is
+    /// followed again the next time it's needed, or right away if the
+    /// failing read used an address from an earlier read.
+    pub fn read(&self, process: &Process) -> Result<T> {
+        let was_resolved = self.resolved.get().is_some();
+        let read = || self.resolve(process).and_then(|addr| process.read(addr));
+        read().or_else(|e| {
+            self.invalidate();
+            if was_resolved {
+                read().inspect_err(|_| self.invalidate())
+            } else {
+                Err(e)
+            }
+        })
+    }
+
+    /// Forget the address the path led to, so it's followed again on the
+    /// next read.
+    pub fn invalidate(&self) {
+        self.resolved.set(None);
+    }
+}
+
+/// A set of named [`DeepPointer`]s which can all be read at once, so that the
+/// table of offsets for a game lives in one place instead of being spread out
+/// over a bunch of fields.
+///
+/// ```no_run
+/// # use livesplit_wrapper::{DeepPointer, Pointers, Process};
+/// # fn f(process: &Process) {
+/// let mut pointers = Pointers::new()
+///     .with("level", DeepPointer::<u32>::new(0x1000, &[0x10, 0x8]))
+///     .with("x", DeepPointer::<f32>::new(0x1000, &[0x18, 0x40]));
+/// pointers.update(process);
+/// if pointers.get::<u32>("level") == Some(3) {
+///     log::info!("at x = {:?}", pointers.get::<f32>("x"));
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Pointers {
+    entries: Vec<Entry>,
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    name: &'static str,
+    base: Base,
+    offsets: Vec<i64>,
+    type_id: TypeId,
+    value: Vec<u8>,
+    valid: bool,
+}
+
+impl Pointers {
+    /// Create an empty set.
+    pub const fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Add a pointer to the set.
+    pub fn with<T: Pod>(mut self, name: &'static str, pointer: DeepPointer<T>) -> Self {
+        self.insert(name, pointer);
+        self
+    }
+
+    /// Add a pointer to the set.
+    pub fn insert<T: Pod>(&mut self, name: &'static str, pointer: DeepPointer<T>) {
+        self.entries.push(Entry {
+            name,
+            base: pointer.base,
+            offsets: pointer.offsets,
+            type_id: TypeId::of::<T>(),
+            value: vec![0; mem::size_of::<T>()],
+            valid: false,
+        });
+    }
+
+    /// Read every pointer in the set, following each path from the start.
+    /// Values that can't be read become `None`
+    /// until the next update.
+    pub fn update(&mut self, process: &Process) {
+        for entry in &mut self.entries {
+            entry.valid = entry
+                .base
+                .address(process)
+                .and_then(|base| process.resolve_offsets(base, &entry.offsets))
+                .and_then(|addr| process.read_into_buf(addr, &mut entry.value))
+                .is_ok();
+        }
+    }
+
+    /// The value of the named pointer as of the last update, or `None` if
+    /// there's no pointer with that name or it couldn't be read.
+    ///
+    /// # Panics
+    ///
+    /// If the pointer was added with a type other than `T`.
+    pub fn get<T: Pod>(&self, name: &str) -> Option<T> {
+        let entry = self.entries.iter().find(|e| e.name == name)?;
+        assert!(
+            entry.type_id == TypeId::of::<T>(),
+            "pointer `{name}` was added as a different type than {}",
+            core::any::type_name::<T>(),
+        );
+        entry
+            .valid
+            .then(|| bytemuck::pod_read_unaligned(&entry.value))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mock;
+
+    #[test]
+    fn reads_named_paths() {
+        mock::with_host(|h| {
+            let p = h.spawn(1, "Game.exe");
+            p.map(0x100, 0x200u64.to_le_bytes());
+            p.map(0x210, 3u32.to_le_bytes());
+            p.map(0x300, 0x400u64.to_le_bytes());
+            p.map(0x408, 1.5f32.to_le_bytes());
+        });
+        let p = Process::attach("Game.exe").unwrap();
+        let mut pointers = Pointers::new()
+            .with("level", DeepPointer::<u32>::new(0x100, &[0x10]))
+            .with("x", DeepPointer::<f32>::new(0x300, &[0x8]))
+            .with("missing", DeepPointer::<u8>::new(0x500, &[0x8]));
+        assert_eq!(pointers.get::<u32>("level"), None);
+        pointers.update(&p);
+        assert_eq!(pointers.get::<u32>("level"), Some(3));
+        assert_eq!(pointers.get::<f32>("x"), Some(1.5));
+        assert_eq!(pointers.get::<u8>("missing"), None);
+        assert_eq!(pointers.get::<u8>("nothing"), None);
+        assert_eq!(
+            DeepPointer::<u32>::new(0x100, &[0x10]).resolve(&p),
+            Ok(0x210)
+        );
+    }
+
+    #[test]
+    fn caches_resolved_address() {
+        mock::with_host(|h| {
+            let p = h.spawn(1, "Game.exe");
+            p.load("game.dll", 0x1000, 0x2000u64.to_le_bytes());
+            p.map(0x2010, 3u32.to_le_bytes());
+            p.map(0x3010, 4u32.to_le_bytes());
+        });
+        let p = Process::attach("Game.exe").unwrap();
+        let level = DeepPointer::<u32>::from_module("game.dll", 0, &[0x10]);
+        let missing = DeepPointer::<u32>::from_module("other.dll", 0, &[0x10]);
+        assert_eq!(
+            missing.read(&p),
+            Err(Error::ModuleNotFound("other.dll".into()))
+        );
+        assert_eq!(level.read(&p), Ok(3));
+
+        // the old address is still readable, so the move goes unnoticed
+        mock::with_host(|h| h.process(1).write(0x1000, &0x3000u64.to_le_bytes()));
+        let reads = mock::with_host(|h| h.reads);
+        assert_eq!(level.read(&p), Ok(3));
+        assert_eq!(mock::with_host(|h| h.reads), reads + 1);
+        level.invalidate();
+        assert_eq!(level.read(&p), Ok(4));
+
+        // once it isn't the path gets followed again
+        mock::with_host(|h| {
+            let p = h.process(1);
+            p.write(0x1000, &0x2000u64.to_le_bytes());
+            p.memory.retain(|(base, _)| *base != 0x3010);
+        });
+        assert_eq!(level.read(&p), Ok(3));
+    }
+}
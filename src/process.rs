@@ -1,20 +1,96 @@
-use std::mem::{self, MaybeUninit};
-use std::slice;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::Write;
+use core::mem::{self, MaybeUninit};
+use core::slice;
 
-pub use bytemuck::Pod;
+pub use bytemuck::{Pod, Zeroable};
 
 use super::ffi;
+use crate::HostFunctions;
 
-/// Currently the only possible error is a failed memory read on the attached
-/// process.
-#[derive(Debug)]
+/// The ways that reading from (or writing to) the attached process can fail.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Error {
-    /// A memory read on the attached process failed
-    FailedRead,
+    /// Reading `len` bytes at `address` failed, usually because some of that
+    /// memory isn't mapped
+    FailedRead {
+        /// Where the read started
+        address: Address,
+        /// How many bytes were being read
+        len: usize,
+    },
+    /// Writing `len` bytes at `address` failed, either because that memory
+    /// isn't mapped or writable, or because the runtime doesn't allow writes
+    FailedWrite {
+        /// Where the write started
+        address: Address,
+        /// How many bytes were being written
+        len: usize,
+    },
+    /// The process has exited, or no process was attached
+    ProcessNotOpen,
+    /// A module that was needed isn't loaded in the process
+    ModuleNotFound(String),
+    /// A field was looked up by a name that doesn't exist
+    FieldNotFound(String),
+    /// A field was read as a type that isn't the same size as the field
+    WrongFieldSize {
+        /// The name of the field
+        name: String,
+        /// The field's size in bytes
+        size: usize,
+    },
+    /// An enum was read but the value doesn't correspond to any variant
+    InvalidDiscriminant(u32),
+    /// The string at this address wasn't terminated, was too long, or wasn't
+    /// valid unicode
+    InvalidString(Address),
+    /// The pointer read at this address was null
+    NullPointer(Address),
+    /// A pointer path ran into a pointer that's out of range for the
+    /// process's pointer width
+    InvalidPointer(Address),
+    /// A float was read but it was NaN or infinite
+    InvalidFloat,
+    /// The number of elements in an array was read but it was too big to be
+    /// believable
+    InvalidCount(u64),
 }
 
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Error::FailedRead { address, len } => {
+                write!(f, "failed to read {len} bytes at {address:#x}")
+            }
+            Error::FailedWrite { address, len } => {
+                write!(f, "failed to write {len} bytes at {address:#x}")
+            }
+            Error::ProcessNotOpen => f.write_str("the process isn't open"),
+            Error::ModuleNotFound(name) => write!(f, "module `{name}` isn't loaded"),
+            Error::FieldNotFound(name) => write!(f, "there's no field named `{name}`"),
+            Error::WrongFieldSize { name, size } => {
+                write!(
+                    f,
+                    "field `{name}` is {size} bytes, which doesn't match how it was read"
+                )
+            }
+            Error::InvalidDiscriminant(n) => write!(f, "{n} isn't a valid enum discriminant"),
+            Error::InvalidString(address) => write!(f, "invalid string at {address:#x}"),
+            Error::NullPointer(address) => write!(f, "null pointer at {address:#x}"),
+            Error::InvalidPointer(address) => write!(f, "invalid pointer {address:#x}"),
+            Error::InvalidFloat => f.write_str("float was NaN or infinite"),
+            Error::InvalidCount(n) => write!(f, "array length {n} is too big"),
+        }
+    }
+}
+
+impl core::error::Error for Error {}
+
 /// The result of an attempt to read process memory.
-pub type Result<T> = std::result::Result<T, Error>;
+pub type Result<T> = core::result::Result<T, Error>;
 
 /// An address in the attached processes memory.
 ///
@@ -22,17 +98,367 @@ pub type Result<T> = std::result::Result<T, Error>;
 /// they try to read outside it's address space.
 pub type Address = u64;
 
+/// The size of pointers in the attached process, which depends on whether the
+/// game is a 32 or 64-bit program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointerWidth {
+    /// 4 byte pointers
+    Bits32,
+    /// 8 byte pointers
+    Bits64,
+}
+
+impl PointerWidth {
+    /// The size of a pointer in bytes.
+    pub const fn size(self) -> usize {
+        match self {
+            PointerWidth::Bits32 => 4,
+            PointerWidth::Bits64 => 8,
+        }
+    }
+}
+
+/// The byte order of values in the attached process. This is almost always
+/// little endian, but emulators for big endian consoles (like the GameCube or
+/// N64) expose the emulated memory as-is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Endian {
+    /// Least significant byte first
+    Little,
+    /// Most significant byte first
+    Big,
+}
+
+/// Values whose byte order can be reversed, so they can be read from a process
+/// with a different byte order than the splitter, see
+/// [`read_be`](Process::read_be).
+pub trait SwapBytes: Pod {
+    /// Reverse the order of the bytes.
+    fn swap_bytes(self) -> Self;
+}
+
+macro_rules! impl_swap_bytes {
+    ($($int:ty),*) => {$(
+        impl SwapBytes for $int {
+            fn swap_bytes(self) -> Self {
+                <$int>::swap_bytes(self)
+            }
+        }
+    )*};
+}
+
+impl_swap_bytes!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl SwapBytes for f32 {
+    fn swap_bytes(self) -> Self {
+        f32::from_bits(self.to_bits().swap_bytes())
+    }
+}
+
+impl SwapBytes for f64 {
+    fn swap_bytes(self) -> Self {
+        f64::from_bits(self.to_bits().swap_bytes())
+    }
+}
+
+/// Arrays have each element swapped, not the whole thing reversed.
+impl<T: SwapBytes, const N: usize> SwapBytes for [T; N]
+where
+    [T; N]: Pod,
+{
+    fn swap_bytes(self) -> Self {
+        self.map(T::swap_bytes)
+    }
+}
+
+/// The address range of a module loaded by the attached process.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Module {
+    /// The address the module was loaded at.
+    pub base: Address,
+    /// The size of the module in bytes.
+    pub size: u64,
+}
+
+/// A range of memory mapped by the attached process, see
+/// [`Process::memory_ranges`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryRange {
+    /// The address the range starts at.
+    pub base: Address,
+    /// The size of the range in bytes.
+    pub size: u64,
+    /// What the range can be used for.
+    pub flags: MemoryRangeFlags,
+}
+
+impl MemoryRange {
+    /// Whether `addr` is inside the range.
+    pub fn contains(&self, addr: Address) -> bool {
+        addr.checked_sub(self.base)
+            .is_some_and(|offset| offset < self.size)
+    }
+
+    /// The range as a [`Module`], so it can be searched with
+    /// [`Process::find_string`].
+    pub fn as_module(&self) -> Module {
+        Module {
+            base: self.base,
+            size: self.size,
+        }
+    }
+}
+
+/// The protection of a [`MemoryRange`], as reported by the runtime. Check
+/// for a flag with [`contains`](MemoryRangeFlags::contains).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryRangeFlags(u64);
+
+impl MemoryRangeFlags {
+    /// The range can be read.
+    pub const READ: Self = Self(1 << 1);
+    /// The range can be written to.
+    pub const WRITE: Self = Self(1 << 2);
+    /// The range can be executed.
+    pub const EXECUTE: Self = Self(1 << 3);
+    /// The range is backed by a file, like a module is.
+    pub const PATH: Self = Self(1 << 4);
+
+    /// Whether every flag in `other` is set.
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+/// Types that can be read out of the attached process's memory with
+/// [`Process::read_struct`]. Unlike [`Pod`] types the fields don't have to be
+/// laid out the way Rust would lay them out, which is handy for game structs
+/// where only a few fields spread out over a big struct are interesting.
+///
+/// With the `derive` feature this can be derived for structs whose fields are
+/// all [`Pod`]. Each field is read at the offset given by its `#[offset]`
+/// attribute, or right after the previous field if it doesn't have one. The
+/// whole struct is read at once.
+///
+/// ```ignore
+/// use livesplit_wrapper::FromMemory;
+///
+/// #[derive(FromMemory)]
+/// struct Player {
+///     #[offset(0x10)]
+///     health: u32,
+///     // at 0x14
+///     max_health: u32,
+///     #[offset(0x38)]
+///     position: [f32; 3],
+/// }
+/// ```
+pub trait FromMemory: Sized {
+    /// Read a value starting at `addr`.
+    fn read_from(process: &Process, addr: Address) -> Result<Self>;
+}
+
+/// Where the length and characters of a Mono `System.String` object are,
+/// relative to the start of the object. The fields come after the object
+/// header, which is two pointers, so they depend on the pointer width.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MonoStringLayout {
+    /// Offset of the `i32` number of UTF-16 code units in the string.
+    pub length: u64,
+    /// Offset of the first UTF-16 code unit.
+    pub chars: u64,
+}
+
+impl MonoStringLayout {
+    /// The standard layout for a runtime with the given pointer width.
+    pub const fn new(width: PointerWidth) -> Self {
+        let header = 2 * width.size() as u64;
+        Self {
+            length: header,
+            chars: header + 4,
+        }
+    }
+}
+
 /// A handle representing an attached process that can be used to read its
 /// memory.
 #[derive(Debug)]
-pub struct Process(pub(crate) u64);
+pub struct Process {
+    handle: u64,
+    name: String,
+    width: PointerWidth,
+    endian: Endian,
+}
 
 impl Process {
+    fn new(handle: u64, name: String) -> Self {
+        Self {
+            handle,
+            name,
+            width: PointerWidth::Bits64,
+            endian: Endian::Little,
+        }
+    }
+
+    pub(crate) fn attach(name: &str) -> Option<Process> {
+        unsafe {
+            match ffi::process_attach(name.as_ptr(), name.len()) {
+                0 => None,
+                n => {
+                    crate::LAST_ATTACHED.set(n);
+                    Some(Process::new(n, name.to_string()))
+                }
+            }
+        }
+    }
+
+    #[cfg(any(test, feature = "process-list"))]
+    pub(crate) fn attach_by_pid(pid: u32) -> Option<Process> {
+        match unsafe { ffi::process_attach_by_pid(pid as u64) } {
+            0 => None,
+            n => {
+                crate::LAST_ATTACHED.set(n);
+                let mut process = Process::new(n, String::new());
+                if let Some(path) = process.path() {
+                    process.name = path.rsplit(['/', '\\']).next().unwrap_or_default().into();
+                }
+                Some(process)
+            }
+        }
+    }
+
+    /// Detach from the process right away instead of waiting for the handle to
+    /// be dropped.
+    pub fn detach(self) {
+        // skip `Drop` so the process doesn't get detached twice
+        let mut this = mem::ManuallyDrop::new(self);
+        drop(mem::take(&mut this.name));
+        this.forget_attached();
+        unsafe { ffi::process_detach(this.handle) }
+    }
+
+    /// Let the runner know this process is going away, so it doesn't keep
+    /// holding on to a handle that's about to stop being valid.
+    fn forget_attached(&self) {
+        if crate::LAST_ATTACHED.get() == self.handle {
+            crate::LAST_ATTACHED.set(0);
+        }
+    }
+
+    /// The runtime's handle for the process. Every attach gets a new handle,
+    /// even when it's to the same process, so a different handle means the
+    /// process was attached to again, not necessarily that the game was
+    /// restarted. Processes compare equal when their handles are equal.
+    pub fn handle(&self) -> u64 {
+        self.handle
+    }
+
+    /// The name that was used to attach to the process. For processes attached
+    /// to by PID this is the file name of the executable, or empty if the
+    /// runtime doesn't know its path (which needs the `process-path`
+    /// feature).
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the path to the process's executable, which can be used to tell
+    /// different releases of a game apart. Returns `None` if the runtime
+    /// can't figure out the path. Asking the runtime requires the
+    /// `process-path` feature, without it this is always `None`.
+    pub fn path(&self) -> Option<String> {
+        #[cfg(any(test, feature = "process-path"))]
+        return crate::read_host_string(|buf, len| unsafe {
+            ffi::process_get_path(self.handle, buf, len)
+        });
+        #[cfg(not(any(test, feature = "process-path")))]
+        None
+    }
+
+    /// The width of pointers in the process, which is used when following
+    /// pointer paths. This defaults to 64 bits since there's no way to ask the
+    /// runtime, so set it yourself with
+    /// [`set_pointer_width`](Process::set_pointer_width) for 32-bit games.
+    pub fn pointer_width(&self) -> PointerWidth {
+        self.width
+    }
+
+    /// Set the width of pointers in the process.
+    pub fn set_pointer_width(&mut self, width: PointerWidth) {
+        self.width = width;
+    }
+
+    /// The byte order used for pointers when following pointer paths and by
+    /// [`read_endian`](Process::read_endian), which defaults to little endian.
+    pub fn endian(&self) -> Endian {
+        self.endian
+    }
+
+    /// Set the byte order of pointers in the process.
+    pub fn set_endian(&mut self, endian: Endian) {
+        self.endian = endian;
+    }
+
+    /// Get the base address of the process's main executable, which is the
+    /// module with the same name that was used to attach.
+    pub fn main_module(&self) -> Option<Address> {
+        self.module(&self.name)
+    }
+
+    /// Check whether the attached process is still running. Once this returns
+    /// `false` every read will fail, so you'll need to attach again.
+    pub fn is_open(&self) -> bool {
+        unsafe { ffi::process_is_open(self.handle) != 0 }
+    }
+
+    /// Whether `addr` could plausibly point to something in the process. This
+    /// rejects null and, for 32-bit processes, anything that doesn't fit in 32
+    /// bits, so pointer paths can bail out early instead of doing a read that
+    /// is bound to fail.
+    pub fn is_likely_valid(&self, addr: Address) -> bool {
+        addr != 0 && (self.width == PointerWidth::Bits64 || addr <= u32::MAX as Address)
+    }
+
     /// Reads a single value from the attached processes memory space. To be
     /// able to use this with your own types, they need to implement [`Pod`]
     /// (it's implemented for the numeric types and fixed size arrays by
     /// default).
+    ///
+    /// [`Pod`] and [`Zeroable`] can be derived for `#[repr(C)]` structs that
+    /// have no padding. The derives expect to find `bytemuck` at the root of
+    /// your crate, so either depend on it yourself or point them at the copy
+    /// re-exported from this crate:
+    ///
+    /// ```no_run
+    /// use livesplit_wrapper::{Pod, Process, Zeroable};
+    ///
+    /// #[derive(Clone, Copy, Pod, Zeroable)]
+    /// #[bytemuck(crate = "livesplit_wrapper::bytemuck")]
+    /// #[repr(C)]
+    /// struct Vec3 {
+    ///     x: f32,
+    ///     y: f32,
+    ///     z: f32,
+    /// }
+    ///
+    /// # fn f(process: Process) -> livesplit_wrapper::Result<()> {
+    /// let position: Vec3 = process.read(0x1234)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Zero sized types like `()` are rejected at compile time, since reading
+    /// one wouldn't actually read anything:
+    ///
+    /// ```compile_fail
+    /// # use livesplit_wrapper::Process;
+    /// fn read_nothing(process: &Process) {
+    ///     process.read::<()>(0x1234);
+    /// }
+    /// # let process: Option<Process> = None;
+    /// # process.as_ref().map(read_nothing);
+    /// ```
     pub fn read<T: Pod>(&self, addr: Address) -> Result<T> {
+        const { assert!(mem::size_of::<T>() > 0, "can't read a zero sized type") };
         unsafe {
             let mut buf = MaybeUninit::uninit();
             self.read_into_buf(
@@ -43,53 +469,2076 @@ impl Process {
         }
     }
 
+    /// Reads a big endian value, which is what emulators for consoles like the
+    /// GameCube, Wii and N64 expose.
+    pub fn read_be<T: SwapBytes>(&self, addr: Address) -> Result<T> {
+        let value: T = self.read(addr)?;
+        Ok(if cfg!(target_endian = "big") {
+            value
+        } else {
+            value.swap_bytes()
+        })
+    }
+
+    /// Reads a little endian value.
+    pub fn read_le<T: SwapBytes>(&self, addr: Address) -> Result<T> {
+        let value: T = self.read(addr)?;
+        Ok(if cfg!(target_endian = "little") {
+            value
+        } else {
+            value.swap_bytes()
+        })
+    }
+
+    /// Reads a value in the process's [`endian`](Process::endian), so a
+    /// splitter for an emulated console only has to set that once.
+    pub fn read_endian<T: SwapBytes>(&self, addr: Address) -> Result<T> {
+        match self.endian {
+            Endian::Little => self.read_le(addr),
+            Endian::Big => self.read_be(addr),
+        }
+    }
+
+    /// Reads a struct that implements [`FromMemory`], which can be derived for
+    /// structs with gaps between the fields.
+    pub fn read_struct<T: FromMemory>(&self, addr: Address) -> Result<T> {
+        T::read_from(self, addr)
+    }
+
+    /// Reads a value, or gives back `default` if the read fails. This is handy
+    /// for values that are expected to be unreadable some of the time, like
+    /// while the game is still starting up.
+    pub fn read_or<T: Pod>(&self, addr: Address, default: T) -> T {
+        self.read(addr).unwrap_or(default)
+    }
+
+    /// Reads a value, or gives back `None` if the read fails.
+    pub fn read_opt<T: Pod>(&self, addr: Address) -> Option<T> {
+        self.read(addr).ok()
+    }
+
+    /// Reads an `f32`, failing with [`Error::InvalidFloat`] if it's NaN or
+    /// infinite. Games sometimes have garbage in a float while it's being set
+    /// up, and it's usually better to skip a tick than to compare against it.
+    pub fn read_f32_checked(&self, addr: Address) -> Result<f32> {
+        let value = self.read::<f32>(addr)?;
+        value
+            .is_finite()
+            .then_some(value)
+            .ok_or(Error::InvalidFloat)
+    }
+
+    /// Same as [`read_f32_checked`](Process::read_f32_checked) but for `f64`.
+    pub fn read_f64_checked(&self, addr: Address) -> Result<f64> {
+        let value = self.read::<f64>(addr)?;
+        value
+            .is_finite()
+            .then_some(value)
+            .ok_or(Error::InvalidFloat)
+    }
+
+    /// Reads a single byte as a boolean, treating any non-zero value as `true`.
+    pub fn read_bool(&self, addr: Address) -> Result<bool> {
+        self.read::<u8>(addr).map(|b| b != 0)
+    }
+
+    /// Reads a 4 byte boolean (like a C `BOOL`), treating any non-zero value as
+    /// `true`.
+    pub fn read_bool32(&self, addr: Address) -> Result<bool> {
+        self.read::<u32>(addr).map(|b| b != 0)
+    }
+
+    /// Reads a pointer of the given width, zero-extending 32-bit pointers.
+    pub fn read_pointer(&self, addr: Address, width: PointerWidth) -> Result<Address> {
+        match width {
+            PointerWidth::Bits32 => self.read::<u32>(addr).map(Address::from),
+            PointerWidth::Bits64 => self.read(addr),
+        }
+    }
+
+    /// Reads a big endian pointer of the given width, zero-extending 32-bit
+    /// pointers.
+    pub fn read_be_pointer(&self, addr: Address, width: PointerWidth) -> Result<Address> {
+        match width {
+            PointerWidth::Bits32 => self.read(addr).map(u32::from_be).map(Address::from),
+            PointerWidth::Bits64 => self.read(addr).map(u64::from_be),
+        }
+    }
+
+    /// Reads a little endian pointer of the given width, zero-extending 32-bit
+    /// pointers.
+    pub fn read_le_pointer(&self, addr: Address, width: PointerWidth) -> Result<Address> {
+        match width {
+            PointerWidth::Bits32 => self.read(addr).map(u32::from_le).map(Address::from),
+            PointerWidth::Bits64 => self.read(addr).map(u64::from_le),
+        }
+    }
+
+    /// Read a pointer of the given width at `ptr_addr` and then read a `T` at
+    /// `offset` from where it points, which is how a field of a struct behind
+    /// a pointer is read. Null pointers give an [`Error::NullPointer`].
+    pub fn read_through<T: Pod>(
+        &self,
+        ptr_addr: Address,
+        offset: i64,
+        width: PointerWidth,
+    ) -> Result<T> {
+        let pointer = self.read_pointer(ptr_addr, width)?;
+        self.check_pointer(ptr_addr, pointer)?;
+        self.read(pointer.wrapping_add_signed(offset))
+    }
+
+    /// Follow a chain of offsets starting at `base` and read a `T` at the end.
+    /// Each offset is added to the pointer read from the previous address, so
+    /// `read_at_offsets(base, &[0x10, 0x8])` reads `T` at `*(*base + 0x10) +
+    /// 0x8`. Pointers are read using the process's
+    /// [`pointer_width`](Process::pointer_width) and
+    /// [`endian`](Process::endian), and with no offsets this is just a
+    /// [`read`](Process::read) at `base`. If any pointer along the way is
+    /// null this stops with an [`Error::NullPointer`], or if it isn't
+    /// [likely to be valid](Process::is_likely_valid) an
+    /// [`Error::InvalidPointer`].
+    pub fn read_at_offsets<T: Pod>(&self, base: Address, offsets: &[i64]) -> Result<T> {
+        self.read(self.resolve_offsets(base, offsets)?)
+    }
+
+    /// Follow a chain of offsets like
+    /// [`read_at_offsets`](Process::read_at_offsets), but return the address
+    /// at the end instead of reading from it.
+    pub fn resolve_offsets(&self, base: Address, offsets: &[i64]) -> Result<Address> {
+        self.follow(base, offsets.iter().copied())
+    }
+
+    /// Follow a chain of pointers starting at `base` and read a `T` at the
+    /// end, the same way as [`read_at_offsets`](Process::read_at_offsets).
+    /// This takes unsigned offsets, which is how they're usually written down
+    /// in tools like Cheat Engine.
+    ///
+    /// ```no_run
+    /// # use livesplit_wrapper::Process;
+    /// # fn f(process: &Process) -> livesplit_wrapper::Result<()> {
+    /// let base = process.main_module().unwrap_or_default();
+    /// let level: u32 = process.read_pointer_path(base + 0x1A2B3C, &[0x10, 0x48])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read_pointer_path<T: Pod>(&self, base: Address, offsets: &[u64]) -> Result<T> {
+        // adding an offset wraps, so reinterpreting it as signed is the same
+        self.read(self.follow(base, offsets.iter().map(|&o| o as i64))?)
+    }
+
+    fn follow(&self, base: Address, offsets: impl Iterator<Item = i64>) -> Result<Address> {
+        let mut addr = base;
+        for offset in offsets {
+            addr = self.follow_one(addr)?.wrapping_add_signed(offset);
+        }
+        Ok(addr)
+    }
+
+    /// Read the pointer at `addr` that a pointer path would follow.
+    pub(crate) fn follow_one(&self, addr: Address) -> Result<Address> {
+        if !self.is_likely_valid(addr) {
+            return Err(Error::InvalidPointer(addr));
+        }
+        let pointer = match self.endian {
+            Endian::Little => self.read_le_pointer(addr, self.width)?,
+            Endian::Big => self.read_be_pointer(addr, self.width)?,
+        };
+        self.check_pointer(addr, pointer)?;
+        Ok(pointer)
+    }
+
+    /// Make sure the `pointer` that was read at `addr` is worth following.
+    fn check_pointer(&self, addr: Address, pointer: Address) -> Result<()> {
+        match pointer {
+            0 => Err(Error::NullPointer(addr)),
+            _ if !self.is_likely_valid(pointer) => Err(Error::InvalidPointer(pointer)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Reads a `u32` and converts it to `E`, which is usually a fieldless enum
+    /// representing some game state. Values that don't match a variant give an
+    /// [`Error::InvalidDiscriminant`] rather than an invalid enum.
+    ///
+    /// ```no_run
+    /// # use livesplit_wrapper::Process;
+    /// enum Screen {
+    ///     Menu,
+    ///     Loading,
+    ///     Playing,
+    /// }
+    ///
+    /// impl TryFrom<u32> for Screen {
+    ///     type Error = ();
+    ///     fn try_from(n: u32) -> Result<Self, ()> {
+    ///         Ok(match n {
+    ///             0 => Screen::Menu,
+    ///             1 => Screen::Loading,
+    ///             2 => Screen::Playing,
+    ///             _ => return Err(()),
+    ///         })
+    ///     }
+    /// }
+    ///
+    /// # fn f(process: Process) -> livesplit_wrapper::Result<()> {
+    /// let screen: Screen = process.read_enum(0x1234)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read_enum<E: TryFrom<u32>>(&self, addr: Address) -> Result<E> {
+        let n = self.read::<u32>(addr)?;
+        E::try_from(n).map_err(|_| Error::InvalidDiscriminant(n))
+    }
+
     /// Search for a module (aka dynamic library) loaded by the attached process
     /// by name and return its base address.
     pub fn module(&self, name: &str) -> Option<Address> {
         unsafe {
-            match ffi::process_get_module_address(self.0, name.as_ptr() as u32, name.len() as u32) {
+            match ffi::process_get_module_address(self.handle, name.as_ptr(), name.len()) {
                 0 => None,
                 n => Some(n),
             }
         }
     }
 
+    /// Like [`module`](Process::module) but also gets the size of the module,
+    /// which is needed to scan through it.
+    pub fn module_range(&self, name: &str) -> Option<Module> {
+        Some(Module {
+            base: self.module(name)?,
+            size: self.module_size(name)?,
+        })
+    }
+
+    /// Look up a symbol exported by the Windows module loaded at `base`, like
+    /// a function or a global variable, by reading the module's PE export
+    /// table. Returns `None` if the module doesn't export it.
+    pub fn pe_export(&self, base: Address, symbol: &str) -> Option<Address> {
+        const MAX_SYMBOL_LEN: usize = 255;
+        let pe: u32 = self.read(base + 0x3C).ok()?;
+        let optional_header = base + pe as u64 + 0x18;
+        // the data directories are further along in PE32+ headers
+        let data_dirs = match self.read::<u16>(optional_header).ok()? {
+            0x20B => 0x70,
+            _ => 0x60,
+        };
+        let exports = base + self.read::<u32>(optional_header + data_dirs).ok()? as u64;
+        let count: u32 = self.read(exports + 0x18).ok()?;
+        let functions = base + self.read::<u32>(exports + 0x1C).ok()? as u64;
+        let names = base + self.read::<u32>(exports + 0x20).ok()? as u64;
+        let ordinals = base + self.read::<u32>(exports + 0x24).ok()? as u64;
+        (0..count as u64).find_map(|i| {
+            let name = base + self.read::<u32>(names + i * 4).ok()? as u64;
+            if self.try_read_cstr(name, MAX_SYMBOL_LEN).ok()? != symbol {
+                return None;
+            }
+            let ordinal: u16 = self.read(ordinals + i * 2).ok()?;
+            let function: u32 = self.read(functions + ordinal as u64 * 4).ok()?;
+            Some(base + function as u64)
+        })
+    }
+
+    /// Every readable range of memory the process has mapped, in the order the
+    /// runtime lists them. This is useful for checking that a pointer is
+    /// actually mapped before following it, or for finding something that
+    /// isn't in any module with [`scan_memory`](Process::scan_memory). Needs
+    /// the `memory-ranges` feature.
+    #[cfg(any(test, feature = "memory-ranges"))]
+    pub fn memory_ranges(&self) -> Vec<MemoryRange> {
+        let count = unsafe { ffi::process_get_memory_range_count(self.handle) };
+        (0..count)
+            .filter_map(|i| unsafe {
+                let range = MemoryRange {
+                    base: ffi::process_get_memory_range_address(self.handle, i),
+                    size: ffi::process_get_memory_range_size(self.handle, i),
+                    flags: MemoryRangeFlags(ffi::process_get_memory_range_flags(self.handle, i)),
+                };
+                (range.size != 0 && range.flags.contains(MemoryRangeFlags::READ)).then_some(range)
+            })
+            .collect()
+    }
+
+    /// Like [`scan_signature`](Process::scan_signature), but searches every
+    /// readable range of memory instead of a single module. This reads the
+    /// whole process so it's slow, only do it once and cache the result.
+    /// Needs the `memory-ranges` feature.
+    ///
+    /// # Panics
+    ///
+    /// If the pattern isn't made up of hex bytes and wildcards.
+    #[cfg(any(test, feature = "memory-ranges"))]
+    pub fn scan_memory(&self, pattern: &str) -> Option<Address> {
+        let pattern = parse_signature(pattern);
+        let mut found = None;
+        for range in self.memory_ranges() {
+            self.scan(&range.as_module(), &pattern, 1, |addr| {
+                found = Some(addr);
+                true
+            });
+            if found.is_some() {
+                break;
+            }
+        }
+        found
+    }
+
+    /// Get the size in bytes of a module loaded by the process, or `None` if
+    /// it isn't loaded.
+    pub fn module_size(&self, name: &str) -> Option<u64> {
+        match unsafe { ffi::process_get_module_size(self.handle, name.as_ptr(), name.len()) } {
+            0 => None,
+            size => Some(size),
+        }
+    }
+
+    /// Find the first of several names for a module that's loaded, for when
+    /// it's called something different depending on the version of the game.
+    /// The runtime can't list every module a process has loaded, so the
+    /// possible names have to be known up front.
+    ///
+    /// ```no_run
+    /// # use livesplit_wrapper::Process;
+    /// # fn f(process: &Process) {
+    /// let mono = process.first_module(&["mono-2.0-bdwgc.dll", "mono.dll"]);
+    /// # }
+    /// ```
+    pub fn first_module<'a>(&self, names: &[&'a str]) -> Option<(&'a str, Module)> {
+        names
+            .iter()
+            .find_map(|&name| Some((name, self.module_range(name)?)))
+    }
+
+    /// Scan a module for the first occurrence of `needle` encoded as UTF-8 and
+    /// return its address. This is handy for finding a known string (like a
+    /// version number) when you don't know where anything is yet. The module
+    /// is read in 4KiB chunks and any chunks that fail to read are skipped.
+    pub fn find_string(&self, module: &Module, needle: &str) -> Option<Address> {
+        let mut found = None;
+        let needle: Vec<_> = needle.bytes().map(Some).collect();
+        self.scan(module, &needle, 1, |addr| {
+            found = Some(addr);
+            true
+        });
+        found
+    }
+
+    /// Scan a module for every pointer to `target`, which helps with finding
+    /// a static path to something once you know where it is. Pointers are
+    /// read with the process's [`endian`](Process::endian), and only
+    /// addresses that are a multiple of the pointer size are checked since
+    /// that's where compilers put them. Chunks that fail to read are skipped
+    /// like in [`find_string`](Process::find_string).
+    pub fn find_pointers_to(
+        &self,
+        module: &Module,
+        target: Address,
+        width: PointerWidth,
+    ) -> Vec<Address> {
+        self.find_pointers_to_aligned(module, target, width, width.size() as u64)
+    }
+
+    /// Same as [`find_pointers_to`](Process::find_pointers_to), but only
+    /// checks addresses that are a multiple of `align`. Use an alignment of 1
+    /// to find pointers in packed structs.
+    pub fn find_pointers_to_aligned(
+        &self,
+        module: &Module,
+        target: Address,
+        width: PointerWidth,
+        align: u64,
+    ) -> Vec<Address> {
+        let bytes = match self.endian {
+            Endian::Little => target.to_le_bytes(),
+            Endian::Big => target.to_be_bytes(),
+        };
+        let needle = match (width, self.endian) {
+            (PointerWidth::Bits64, _) => &bytes[..],
+            (PointerWidth::Bits32, _) if target > u32::MAX as Address => return Vec::new(),
+            (PointerWidth::Bits32, Endian::Little) => &bytes[..4],
+            (PointerWidth::Bits32, Endian::Big) => &bytes[4..],
+        };
+        let needle: Vec<_> = needle.iter().copied().map(Some).collect();
+        let mut found = Vec::new();
+        self.scan(module, &needle, align.max(1), |addr| {
+            found.push(addr);
+            false
+        });
+        found
+    }
+
+    /// Scan the named module for a byte pattern and return the address of the
+    /// first match. The pattern is written as hex bytes separated by spaces,
+    /// with `??` (or `?`) for bytes that can be anything, like `"48 8B ?? ??
+    /// 89 05"`. Signatures like this can find code or data that moves around
+    /// between versions of a game, since the bytes around it usually stay the
+    /// same.
+    ///
+    /// # Panics
+    ///
+    /// If the pattern isn't made up of hex bytes and wildcards.
+    pub fn scan_signature(&self, module: &str, pattern: &str) -> Option<Address> {
+        let pattern = parse_signature(pattern);
+        let module = self.module_range(module)?;
+        let mut found = None;
+        self.scan(&module, &pattern, 1, |addr| {
+            found = Some(addr);
+            true
+        });
+        found
+    }
+
+    /// Look for `needle` at every multiple of `align` in the module, stopping
+    /// early if `found` returns `true`. `None` in the needle matches any byte.
+    fn scan(
+        &self,
+        module: &Module,
+        needle: &[Option<u8>],
+        align: u64,
+        mut found: impl FnMut(Address) -> bool,
+    ) {
+        const CHUNK_SIZE: usize = 4096;
+        if needle.is_empty() {
+            return;
+        }
+        // the end of the previous chunk is kept at the start of the buffer so
+        // that matches spanning two chunks are still found
+        let overlap = needle.len() - 1;
+        let mut buf = vec![0u8; overlap + CHUNK_SIZE];
+        let mut kept = 0;
+        let (mut addr, end) = (module.base, module.base + module.size);
+        while addr < end {
+            let len = (end - addr).min(CHUNK_SIZE as u64) as usize;
+            if self
+                .read_into_buf(addr, &mut buf[kept..kept + len])
+                .is_err()
+            {
+                kept = 0;
+                addr += len as u64;
+                continue;
+            }
+            let window = &buf[..kept + len];
+            let window_addr = addr - kept as u64;
+            for (i, w) in window.windows(needle.len()).enumerate() {
+                let at = window_addr + i as u64;
+                let matches = w.iter().zip(needle).all(|(b, n)| n.is_none_or(|n| n == *b));
+                if at.is_multiple_of(align) && matches && found(at) {
+                    return;
+                }
+            }
+            let start = window.len().saturating_sub(overlap);
+            kept = window.len() - start;
+            buf.copy_within(start..start + kept, 0);
+            addr += len as u64;
+        }
+    }
+
     /// Read bytes from the attached processes memory space starting at `addr`
-    /// into `buf`.
+    /// into `buf`. The runtime doesn't report how many bytes it read, only
+    /// whether the whole read worked, so a read that runs into unmapped memory
+    /// partway through fails as a whole and `buf` shouldn't be trusted. Use
+    /// [`read_partial`](Process::read_partial) to get whatever is readable.
     pub fn read_into_buf(&self, addr: Address, buf: &mut [u8]) -> Result<()> {
-        unsafe {
-            (ffi::process_read(self.0, addr, buf.as_mut_ptr() as u32, buf.len() as u32) != 0)
-                .then_some(())
-                .ok_or(Error::FailedRead)
+        match unsafe { ffi::process_read(self.handle, addr, buf.as_mut_ptr(), buf.len()) } {
+            0 => Err(self.read_error(addr, buf.len())),
+            _ => Ok(()),
+        }
+    }
+
+    /// Write `value` to the attached process's memory at `addr`, for things
+    /// like practice tools that need to change what the game is doing.
+    /// Needs the `process-write` feature, and a runtime that allows it:
+    /// runtimes that don't give an [`Error::FailedWrite`].
+    #[cfg(any(test, feature = "process-write"))]
+    pub fn write<T: Pod>(&self, addr: Address, value: T) -> Result<()> {
+        self.write_into_buf(addr, bytemuck::bytes_of(&value))
+    }
+
+    /// Write all of `buf` to the attached process's memory starting at
+    /// `addr`. Like with reads, a write that runs into memory that can't be
+    /// written partway through fails as a whole, but some of it might have
+    /// been written anyway. Needs the `process-write` feature.
+    #[cfg(any(test, feature = "process-write"))]
+    pub fn write_into_buf(&self, addr: Address, buf: &[u8]) -> Result<()> {
+        match unsafe { ffi::process_write(self.handle, addr, buf.as_ptr(), buf.len()) } {
+            0 if !self.is_open() => Err(Error::ProcessNotOpen),
+            0 => Err(Error::FailedWrite {
+                address: addr,
+                len: buf.len(),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Figure out why a read failed, which only costs anything once it has.
+    fn read_error(&self, address: Address, len: usize) -> Error {
+        match self.is_open() {
+            true => Error::FailedRead { address, len },
+            false => Error::ProcessNotOpen,
+        }
+    }
+
+    /// Read as much of `buf` as possible, which is useful when a region might
+    /// run up against unmapped memory. Returns how many bytes at the start of
+    /// `buf` were filled, or an error if not even the first byte could be read.
+    pub fn read_partial(&self, addr: Address, buf: &mut [u8]) -> Result<usize> {
+        let (mut done, mut chunk) = (0, buf.len());
+        // keep halving the chunk size until we find the end of readable memory
+        while chunk > 0 && done < buf.len() {
+            let len = chunk.min(buf.len() - done);
+            match self.read_into_buf(addr + done as u64, &mut buf[done..done + len]) {
+                Ok(()) => done += len,
+                Err(_) => chunk /= 2,
+            }
+        }
+        match done {
+            0 if !buf.is_empty() => Err(self.read_error(addr, buf.len())),
+            n => Ok(n),
+        }
+    }
+
+    /// Reads an array of `count` pointers to null terminated strings starting
+    /// at `base`, like a table of level names, and then reads each string
+    /// with [`read_cstr`](Process::read_cstr).
+    pub fn read_string_table(
+        &self,
+        base: Address,
+        count: usize,
+        width: PointerWidth,
+    ) -> Result<Vec<String>> {
+        (0..count)
+            .map(|i| self.read_cstr(self.read_pointer(base + (i * width.size()) as u64, width)?))
+            .collect()
+    }
+
+    /// Reads a C# string from a Unity game (or anything else using Mono) given
+    /// a pointer to the `System.String` object. The layout is picked based on
+    /// the process's [`pointer_width`](Process::pointer_width), use
+    /// [`read_mono_string_with`](Process::read_mono_string_with) if the game
+    /// does something different.
+    pub fn read_mono_string(&self, object: Address) -> Result<String> {
+        self.read_mono_string_with(object, MonoStringLayout::new(self.width))
+    }
+
+    /// Reads a C# string using the given object layout. Lengths that are
+    /// negative or implausibly long (over 64K characters) give an
+    /// [`Error::InvalidString`], since they usually mean the pointer didn't
+    /// actually point to a string.
+    pub fn read_mono_string_with(
+        &self,
+        object: Address,
+        layout: MonoStringLayout,
+    ) -> Result<String> {
+        const MAX_MONO_STR_LEN: usize = 0x10000;
+        if !self.is_likely_valid(object) {
+            return Err(Error::InvalidPointer(object));
+        }
+        let len = usize::try_from(self.read::<i32>(object + layout.length)?)
+            .ok()
+            .filter(|&len| len <= MAX_MONO_STR_LEN)
+            .ok_or(Error::InvalidString(object))?;
+        let mut units = vec![0u16; len];
+        self.read_into_buf(object + layout.chars, bytemuck::cast_slice_mut(&mut units))?;
+        char::decode_utf16(units)
+            .collect::<core::result::Result<_, _>>()
+            .map_err(|_| Error::InvalidString(object))
+    }
+
+    /// Reads an array whose length and location are stored separately, like a
+    /// `{ u32 count; T *items; }` struct. The `u32` count is read from
+    /// `count_addr` and the pointer to the items (of the given `width`) from
+    /// `ptr_addr`, and then the whole array is read at once. Counts over 65536
+    /// give an [`Error::InvalidCount`], since that's almost always garbage
+    /// being read before the game has set things up.
+    pub fn read_array_via_count<T: Pod>(
+        &self,
+        count_addr: Address,
+        ptr_addr: Address,
+        width: PointerWidth,
+    ) -> Result<Vec<T>> {
+        const MAX_COUNT: u32 = 0x10000;
+        let count = self.read::<u32>(count_addr)?;
+        if count > MAX_COUNT {
+            return Err(Error::InvalidCount(count.into()));
+        }
+        let mut items = vec![T::zeroed(); count as usize];
+        if count > 0 {
+            let ptr = self.read_pointer(ptr_addr, width)?;
+            self.check_pointer(ptr_addr, ptr)?;
+            self.read_into_buf(ptr, bytemuck::cast_slice_mut(&mut items))?;
         }
+        Ok(items)
+    }
+
+    /// Reads `N` values laid out one after another starting at `base`, all at
+    /// once and without allocating.
+    pub fn read_array<T: Pod, const N: usize>(&self, base: Address) -> Result<[T; N]> {
+        let mut items = [T::zeroed(); N];
+        self.read_into_buf(base, bytemuck::cast_slice_mut(&mut items))?;
+        Ok(items)
+    }
+
+    /// Fill several buffers from different addresses, using as few reads as
+    /// possible. Requests that are close together are read all at once, which
+    /// is much cheaper than reading each of them separately when there are a
+    /// lot of values to check every tick. Returns whether each request
+    /// succeeded, in the same order as `requests`.
+    ///
+    /// ```no_run
+    /// # use livesplit_wrapper::Process;
+    /// # fn f(process: &Process) {
+    /// let (mut level, mut deaths) = ([0; 4], [0; 4]);
+    /// let results = process.read_many(&mut [(0x1000, &mut level), (0x1010, &mut deaths)]);
+    /// # }
+    /// ```
+    pub fn read_many(&self, requests: &mut [(Address, &mut [u8])]) -> Vec<Result<()>> {
+        // how far apart requests can be and still get read together
+        const MAX_GAP: u64 = 64;
+        const MAX_SPAN: u64 = 0x1000;
+        let end = |(addr, buf): &(Address, &mut [u8])| addr.saturating_add(buf.len() as u64);
+        let mut results: Vec<Result<()>> = requests.iter().map(|_| Ok(())).collect();
+        let mut order: Vec<usize> = (0..requests.len()).collect();
+        order.sort_unstable_by_key(|&i| requests[i].0);
+        let mut buf = Vec::new();
+        let mut rest = &order[..];
+        while let Some(&first) = rest.first() {
+            let start = requests[first].0;
+            let mut group_end = end(&requests[first]);
+            let len = rest
+                .iter()
+                .take_while(|&&i| {
+                    let next_end = group_end.max(end(&requests[i]));
+                    let close = requests[i].0 <= group_end.saturating_add(MAX_GAP)
+                        && next_end - start <= MAX_SPAN;
+                    if close {
+                        group_end = next_end;
+                    }
+                    close
+                })
+                .count()
+                .max(1);
+            let (group, next) = rest.split_at(len);
+            rest = next;
+            buf.clear();
+            if group.len() > 1 {
+                buf.resize((group_end - start) as usize, 0);
+            }
+            if !buf.is_empty() && self.read_into_buf(start, &mut buf).is_ok() {
+                for &i in group {
+                    let (addr, out) = &mut requests[i];
+                    let offset = (*addr - start) as usize;
+                    out.copy_from_slice(&buf[offset..offset + out.len()]);
+                }
+            } else {
+                // read them one at a time so one bad request doesn't fail the rest
+                for &i in group {
+                    let (addr, out) = &mut requests[i];
+                    results[i] = self.read_into_buf(*addr, out);
+                }
+            }
+        }
+        results
+    }
+
+    /// Read `total_len` bytes starting at `addr` in pieces of at most `chunk`
+    /// bytes, which is handy for going through regions that are too big to
+    /// read all at once. The iterator stops after the first failed read.
+    ///
+    /// # Panics
+    ///
+    /// If `chunk` is 0.
+    pub fn read_chunks(
+        &self,
+        addr: Address,
+        total_len: usize,
+        chunk: usize,
+    ) -> impl Iterator<Item = Result<Vec<u8>>> + '_ {
+        assert!(chunk > 0, "chunk size must be positive");
+        let mut failed = false;
+        (0..total_len).step_by(chunk).map_while(move |offset| {
+            if failed {
+                return None;
+            }
+            let mut buf = vec![0u8; chunk.min(total_len - offset)];
+            let read = self.read_into_buf(addr + offset as u64, &mut buf);
+            failed = read.is_err();
+            Some(read.map(|()| buf))
+        })
+    }
+
+    /// Read a region of memory and format it as a hex dump, with the address,
+    /// 16 bytes in hex, and then those bytes as ASCII on each line. This is
+    /// meant for poking around while figuring out a game's memory layout, for
+    /// example with `log::info!("{}", process.dump(addr, 64)?)`. At most 4KiB
+    /// will be dumped, anything past that is ignored.
+    pub fn dump(&self, addr: Address, len: usize) -> Result<String> {
+        const MAX_DUMP_LEN: usize = 4096;
+        let mut buf = vec![0u8; len.min(MAX_DUMP_LEN)];
+        self.read_into_buf(addr, &mut buf)?;
+        let mut out = String::new();
+        for (i, line) in buf.chunks(16).enumerate() {
+            write!(out, "{:016x} ", addr + i as u64 * 16).unwrap();
+            for b in line {
+                write!(out, " {b:02x}").unwrap();
+            }
+            let padding = (16 - line.len()) * 3;
+            write!(out, "{:padding$}  |", "").unwrap();
+            out.extend(line.iter().map(|&b| match b {
+                0x20..=0x7e => b as char,
+                _ => '.',
+            }));
+            out.push_str("|\n");
+        }
+        Ok(out)
     }
 
     /// Reads a null terminated string starting at the given base address.
-    /// Returns an `Error` on a failed read, and panics if no null is
-    /// encountered after 255 bytes or the bytes read are invalid unicode.
+    /// Returns an `Error` on a failed read. Strings longer than 255 bytes are
+    /// cut off, and invalid unicode is replaced, see
+    /// [`read_cstr_n`](Process::read_cstr_n). Use
+    /// [`try_read_cstr`](Process::try_read_cstr) to get an error instead.
     pub fn read_cstr(&self, base: u64) -> Result<String> {
-        const MAX_STR_LEN: usize = 256;
-        let mut buf = vec![0u8; MAX_STR_LEN];
-        unsafe {
-            (ffi::process_read(
-                self.0,
-                base,
-                buf.as_mut_ptr() as u32,
-                MAX_STR_LEN as u32 - 1,
-            ) != 0)
-                .then_some(())
-                .ok_or(Error::FailedRead)?;
+        self.read_cstr_n::<256>(base)
+    }
+
+    /// Reads a null terminated string of at most `N - 1` bytes starting at
+    /// `base`, using a buffer on the stack rather than the heap. Exactly
+    /// `N - 1` bytes are read, so this fails if that runs into unmapped
+    /// memory even when the string itself is shorter. Longer strings are cut
+    /// off, and invalid unicode is replaced with `�`.
+    pub fn read_cstr_n<const N: usize>(&self, base: Address) -> Result<String> {
+        const { assert!(N > 0, "need room for the null terminator") };
+        let mut buf = [0u8; N];
+        // leave the last byte zeroed so there's always a terminator
+        self.read_into_buf(base, &mut buf[..N - 1])?;
+        let len = buf.iter().position(|&b| b == 0).unwrap_or(N - 1);
+        Ok(String::from_utf8_lossy(&buf[..len]).into_owned())
+    }
+
+    /// Reads a null terminated string of at most `max_len` bytes starting at
+    /// `base`. Unlike [`read_cstr`](Process::read_cstr) a string that's too
+    /// long or isn't valid UTF-8 gives an [`Error::InvalidString`] instead of
+    /// being cut off or patched up, which usually means the pointer to it was
+    /// wrong. Only as much memory as can be read is searched for the null, so
+    /// short strings right before unmapped memory still work.
+    pub fn try_read_cstr(&self, base: Address, max_len: usize) -> Result<String> {
+        let mut buf = vec![0u8; max_len.saturating_add(1)];
+        let read = self.read_partial(base, &mut buf)?;
+        let len = buf[..read]
+            .iter()
+            .position(|&b| b == 0)
+            .ok_or(Error::InvalidString(base))?;
+        buf.truncate(len);
+        String::from_utf8(buf).map_err(|_| Error::InvalidString(base))
+    }
+
+    /// Reads a null terminated string starting at `base` into `buf`, without
+    /// allocating, which is handy for names that get checked every tick.
+    /// Like [`try_read_cstr`](Process::try_read_cstr) only as much as can be
+    /// read is searched for the null. A string that doesn't fit in `buf` is
+    /// cut off, but one that isn't valid UTF-8 gives an
+    /// [`Error::InvalidString`].
+    ///
+    /// ```no_run
+    /// # use livesplit_wrapper::Process;
+    /// # fn f(process: &Process) {
+    /// let mut buf = [0; 64];
+    /// if process.read_cstr_into(0x1000, &mut buf) == Ok("Title Screen") {
+    ///     log::info!("back at the title screen");
+    /// }
+    /// # }
+    /// ```
+    pub fn read_cstr_into<'a>(&self, base: Address, buf: &'a mut [u8]) -> Result<&'a str> {
+        let read = self.read_partial(base, buf)?;
+        let (bytes, terminated) = match buf[..read].iter().position(|&b| b == 0) {
+            Some(len) => (&buf[..len], true),
+            None => (&buf[..read], false),
+        };
+        match core::str::from_utf8(bytes) {
+            Ok(s) => Ok(s),
+            // cutting the string off can split the last character
+            Err(e) if !terminated && e.error_len().is_none() => {
+                Ok(core::str::from_utf8(&bytes[..e.valid_up_to()]).unwrap_or_default())
+            }
+            Err(_) => Err(Error::InvalidString(base)),
         }
-        buf.truncate(buf.iter().position(|&x| x == 0).expect("string too long") + 1);
-        let cstr = std::ffi::CString::from_vec_with_nul(buf).expect("invalid unicode");
-        Ok(cstr.to_string_lossy().to_string())
+    }
+
+    /// Reads a null terminated UTF-16 string starting at the given base
+    /// address, which is how most Windows games store text. Strings longer
+    /// than 255 characters are cut off, see
+    /// [`read_wstr_n`](Process::read_wstr_n).
+    pub fn read_wstr(&self, base: Address) -> Result<String> {
+        self.read_wstr_n::<256>(base)
+    }
+
+    /// Reads a null terminated little endian UTF-16 string of at most `N - 1`
+    /// code units starting at `base`, using a buffer on the stack. Like
+    /// [`read_cstr_n`](Process::read_cstr_n) exactly `N - 1` code units are
+    /// read, longer strings are cut off, and invalid unicode is replaced with
+    /// `�`.
+    pub fn read_wstr_n<const N: usize>(&self, base: Address) -> Result<String> {
+        const { assert!(N > 0, "need room for the null terminator") };
+        let mut buf = [0u16; N];
+        self.read_into_buf(base, bytemuck::cast_slice_mut(&mut buf[..N - 1]))?;
+        let len = buf.iter().position(|&c| c == 0).unwrap_or(N - 1);
+        Ok(
+            char::decode_utf16(buf[..len].iter().map(|&c| u16::from_le(c)))
+                .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+                .collect(),
+        )
+    }
+
+    /// Reads a string stored in a fixed size field, like a `char name[32]` in
+    /// a struct. Exactly `len` bytes are read and the string ends at the first
+    /// null, or fills the whole field if there isn't one. Unlike
+    /// [`read_cstr`](Process::read_cstr) this never reads past the end of the
+    /// field. Invalid unicode is replaced with `�`.
+    pub fn read_fixed_string(&self, addr: Address, len: usize) -> Result<String> {
+        let mut buf = vec![0u8; len];
+        self.read_into_buf(addr, &mut buf)?;
+        let end = buf.iter().position(|&b| b == 0).unwrap_or(len);
+        Ok(String::from_utf8_lossy(&buf[..end]).into_owned())
+    }
+
+    /// Reads a UTF-8 string that's stored as its length followed by the
+    /// bytes, like a Pascal string. The length is a `len_size` byte unsigned
+    /// integer, which has to be 1, 2, 4 or 8. Lengths over 4KiB give an
+    /// [`Error::InvalidString`], use
+    /// [`read_len_prefixed_string_with_max`](Process::read_len_prefixed_string_with_max)
+    /// if longer strings are expected.
+    pub fn read_len_prefixed_string(&self, addr: Address, len_size: usize) -> Result<String> {
+        self.read_len_prefixed_string_with_max(addr, len_size, 4096)
+    }
+
+    /// Same as [`read_len_prefixed_string`](Process::read_len_prefixed_string)
+    /// but with a custom maximum length.
+    pub fn read_len_prefixed_string_with_max(
+        &self,
+        addr: Address,
+        len_size: usize,
+        max_len: usize,
+    ) -> Result<String> {
+        let len = match len_size {
+            1 => self.read::<u8>(addr)? as u64,
+            2 => self.read::<u16>(addr)? as u64,
+            4 => self.read::<u32>(addr)? as u64,
+            8 => self.read::<u64>(addr)?,
+            _ => panic!("length prefix must be 1, 2, 4 or 8 bytes, not {len_size}"),
+        };
+        let len = usize::try_from(len)
+            .ok()
+            .filter(|&len| len <= max_len)
+            .ok_or(Error::InvalidString(addr))?;
+        let mut buf = vec![0u8; len];
+        self.read_into_buf(addr + len_size as u64, &mut buf)?;
+        String::from_utf8(buf).map_err(|_| Error::InvalidString(addr))
     }
 }
 
+/// Parse a signature like `"48 8B ?? 05"` into bytes, with `None` for the
+/// wildcards.
+fn parse_signature(pattern: &str) -> Vec<Option<u8>> {
+    pattern
+        .split_whitespace()
+        .map(|byte| match byte {
+            "?" | "??" => None,
+            _ => Some(
+                u8::from_str_radix(byte, 16)
+                    .unwrap_or_else(|_| panic!("invalid byte `{byte}` in signature")),
+            ),
+        })
+        .collect()
+}
+
+impl PartialEq for Process {
+    fn eq(&self, other: &Self) -> bool {
+        self.handle == other.handle
+    }
+}
+
+impl Eq for Process {}
+
 impl Drop for Process {
     fn drop(&mut self) {
+        self.forget_attached();
         unsafe {
-            ffi::process_detach(self.0);
+            ffi::process_detach(self.handle);
+        }
+    }
+}
+
+/// A process that gets attached to by name and transparently re-attached if
+/// the game closes, which saves tracking a `Option<Process>` and checking
+/// [`is_open`](Process::is_open) yourself.
+///
+/// Attaching is attempted at most once per tick, so polling a game that isn't
+/// running is cheap. That can be spaced out further with
+/// [`with_retry_interval`](GameProcess::with_retry_interval).
+///
+/// Reading right after the game starts often fails since the DLL with the
+/// interesting code isn't loaded yet, so a module to wait for can be given
+/// with [`wait_for_module`](GameProcess::wait_for_module). The process is
+/// then only handed out once that module has been loaded.
+#[derive(Debug)]
+pub struct GameProcess {
+    name: String,
+    process: Option<Process>,
+    last_checked: Option<u64>,
+    last_attempt: Option<u64>,
+    attached_tick: Option<u64>,
+    retry_interval: u64,
+    wait_for: Option<(String, Option<Module>)>,
+}
+
+impl GameProcess {
+    /// Create a handle for the process with the given name. Nothing is
+    /// attached until the first time it's used.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            process: None,
+            last_checked: None,
+            last_attempt: None,
+            attached_tick: None,
+            retry_interval: 1,
+            wait_for: None,
+        }
+    }
+
+    /// Shorthand for `GameProcess::new(process).wait_for_module(module)`.
+    pub fn attach_and_wait_for_module(
+        process: impl Into<String>,
+        module: impl Into<String>,
+    ) -> Self {
+        Self::new(process).wait_for_module(module)
+    }
+
+    /// Don't hand out the process until it has loaded the module called
+    /// `name`, which is looked up once per tick until it shows up.
+    pub fn wait_for_module(mut self, name: impl Into<String>) -> Self {
+        self.wait_for = Some((name.into(), None));
+        self
+    }
+
+    /// The module given to [`wait_for_module`](GameProcess::wait_for_module),
+    /// once it's been loaded.
+    pub fn module(&mut self) -> Option<Module> {
+        self.get()?;
+        self.wait_for.as_ref()?.1
+    }
+
+    /// While the game isn't running, only try attaching every `ticks` ticks
+    /// instead of every tick. A game that closes is still re-attached to on
+    /// the next tick.
+    pub fn with_retry_interval(mut self, ticks: u64) -> Self {
+        self.retry_interval = ticks.max(1);
+        self
+    }
+
+    /// Get the attached process, attaching again first if it has closed since
+    /// the last tick.
+    pub fn get(&mut self) -> Option<&Process> {
+        let tick = crate::current_tick();
+        if self.last_checked != Some(tick) {
+            self.last_checked = Some(tick);
+            if !self.process.as_ref().is_some_and(Process::is_open) {
+                let closed = self.process.take().is_some();
+                let due = self
+                    .last_attempt
+                    .is_none_or(|last| tick - last >= self.retry_interval);
+                if closed || due {
+                    self.last_attempt = Some(tick);
+                    self.process = Process::attach(&self.name);
+                    self.attached_tick = self.process.as_ref().map(|_| tick);
+                    if let Some((_, module)) = &mut self.wait_for {
+                        *module = None;
+                    }
+                }
+            }
+            if let (Some(process), Some((name, module @ None))) =
+                (&self.process, &mut self.wait_for)
+            {
+                *module = process.module_range(name);
+                if module.is_some() {
+                    self.attached_tick = Some(tick);
+                }
+            }
+        }
+        match &self.wait_for {
+            Some((_, None)) => None,
+            _ => self.process.as_ref(),
+        }
+    }
+
+    /// Whether the process was attached on this tick, either for the first
+    /// time or after the game restarted. When waiting for a module this is
+    /// the tick it was found instead. This is the place to reset anything
+    /// that was read from the old process.
+    pub fn just_attached(&mut self) -> bool {
+        self.get().is_some() && self.attached_tick == self.last_checked
+    }
+
+    /// Same as [`Process::read`] but fails if the process isn't attached (or
+    /// the module it's waiting for isn't loaded yet).
+    pub fn read<T: Pod>(&mut self, addr: Address) -> Result<T> {
+        self.get().ok_or(Error::ProcessNotOpen)?.read(addr)
+    }
+}
+
+/// A cached attachment to a process, for when the name isn't known up front
+/// or comes from somewhere else each tick. Unlike [`GameProcess`] the name is
+/// passed in every time, and the process is only attached again once the
+/// cached one has closed.
+#[derive(Debug, Default)]
+pub struct OnceProcess {
+    process: Option<Process>,
+}
+
+impl OnceProcess {
+    /// Create a cache with nothing attached yet.
+    pub const fn new() -> Self {
+        Self { process: None }
+    }
+
+    /// Get the cached process, or attach to `name` if nothing is attached or
+    /// the cached process has closed.
+    pub fn get_or_attach(&mut self, host: &impl HostFunctions, name: &str) -> Option<&Process> {
+        if !self.process.as_ref().is_some_and(Process::is_open) {
+            self.process = host.attach(name);
+        }
+        self.process.as_ref()
+    }
+
+    /// The cached process, without trying to attach.
+    pub fn get(&self) -> Option<&Process> {
+        self.process.as_ref()
+    }
+}
+
+/// Waits for a module to be loaded, since DLLs often show up a while after the
+/// game starts. Until the module is found it gets looked up every time, but
+/// after that the base address is remembered for as long as the same
+/// [`Process`] is used. Attaching again, even to the same game, gets a new
+/// handle, so the module is looked up again then.
+#[derive(Debug, Clone)]
+pub struct ModuleWatcher {
+    name: String,
+    found: Option<(u64, Address)>,
+    resolved_tick: Option<u64>,
+}
+
+impl ModuleWatcher {
+    /// Watch for the module called `name`.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            found: None,
+            resolved_tick: None,
+        }
+    }
+
+    /// The base address of the module, or `None` if it isn't loaded yet.
+    pub fn get(&mut self, process: &Process) -> Option<Address> {
+        match self.found {
+            Some((handle, base)) if handle == process.handle => Some(base),
+            _ => {
+                let base = process.module(&self.name);
+                self.found = base.map(|base| (process.handle, base));
+                self.resolved_tick = base.map(|_| crate::current_tick());
+                base
+            }
+        }
+    }
+
+    /// The tick on which the module was found, if it has been.
+    pub fn resolved_tick(&self) -> Option<u64> {
+        self.resolved_tick
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{mock, Host};
+
+    /// Attach to the fake process with the given ID without needing a name.
+    fn process(id: u64) -> Process {
+        Process::new(mock::with_host(|h| h.open(id)), String::new())
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum Screen {
+        Menu,
+        Playing,
+    }
+
+    impl TryFrom<u32> for Screen {
+        type Error = ();
+        fn try_from(n: u32) -> core::result::Result<Self, ()> {
+            match n {
+                0 => Ok(Screen::Menu),
+                1 => Ok(Screen::Playing),
+                _ => Err(()),
+            }
+        }
+    }
+
+    #[test]
+    fn errors_say_what_failed() {
+        mock::with_host(|h| h.process(1).map(0x100, [0; 4]));
+        let p = process(1);
+        let err = p.read::<u64>(0x100).unwrap_err();
+        assert_eq!(err.to_string(), "failed to read 8 bytes at 0x100");
+        mock::with_host(|h| h.process(1).closed = true);
+        assert_eq!(p.read::<u8>(0x100), Err(Error::ProcessNotOpen));
+        assert_eq!(
+            Error::ModuleNotFound("mono.dll".into()).to_string(),
+            "module `mono.dll` isn't loaded"
+        );
+    }
+
+    #[derive(Debug, PartialEq, livesplit_wrapper_derive::FromMemory)]
+    struct Player {
+        #[offset(0x10)]
+        health: u32,
+        max_health: u16,
+        #[offset(0x4)]
+        position: [f32; 2],
+    }
+
+    #[test]
+    fn read_struct() {
+        let mut player = vec![0; 0x18];
+        player[0x4..0xC].copy_from_slice(&[1.5f32, -2.0].map(f32::to_le_bytes).concat());
+        player[0x10..0x14].copy_from_slice(&80u32.to_le_bytes());
+        player[0x14..0x16].copy_from_slice(&100u16.to_le_bytes());
+        mock::with_host(|h| h.process(1).map(0x100, player));
+        let p = process(1);
+        assert_eq!(
+            p.read_struct(0x100),
+            Ok(Player {
+                health: 80,
+                max_health: 100,
+                position: [1.5, -2.0],
+            })
+        );
+        assert_eq!(
+            p.read_struct::<Player>(0x104),
+            Err(Error::FailedRead {
+                address: 0x104,
+                len: 0x16
+            })
+        );
+    }
+
+    #[test]
+    fn read_enum() {
+        mock::with_host(|h| h.process(1).map(0x100, [1, 0, 0, 0, 7, 0, 0, 0]));
+        let p = process(1);
+        assert_eq!(p.read_enum(0x100), Ok(Screen::Playing));
+        assert_eq!(
+            p.read_enum::<Screen>(0x104),
+            Err(Error::InvalidDiscriminant(7))
+        );
+        assert_eq!(
+            p.read_enum::<Screen>(0x200),
+            Err(Error::FailedRead {
+                address: 0x200,
+                len: 4
+            })
+        );
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Pod, Zeroable)]
+    #[bytemuck(crate = "crate::bytemuck")]
+    #[repr(C)]
+    struct Vec3 {
+        x: f32,
+        y: f32,
+        z: f32,
+    }
+
+    #[test]
+    fn read_derived_struct() {
+        let bytes: Vec<u8> = [1.0f32, -2.5, 1e3]
+            .iter()
+            .flat_map(|f| f.to_le_bytes())
+            .collect();
+        mock::with_host(|h| h.process(1).map(0x100, bytes));
+        let v = process(1).read::<Vec3>(0x100);
+        assert_eq!(
+            v,
+            Ok(Vec3 {
+                x: 1.0,
+                y: -2.5,
+                z: 1e3
+            })
+        );
+    }
+
+    #[test]
+    fn read_bool() {
+        mock::with_host(|h| h.process(1).map(0x100, [0, 1, 0xFF, 0, 0, 0, 0, 2]));
+        let p = process(1);
+        assert_eq!(p.read_bool(0x100), Ok(false));
+        assert_eq!(p.read_bool(0x101), Ok(true));
+        assert_eq!(p.read_bool(0x102), Ok(true));
+        assert_eq!(p.read_bool32(0x103), Ok(false));
+        assert_eq!(p.read_bool32(0x104), Ok(true));
+        assert_eq!(
+            p.read_bool32(0x105),
+            Err(Error::FailedRead {
+                address: 0x105,
+                len: 4
+            })
+        );
+    }
+
+    #[test]
+    fn read_pointer() {
+        let bytes = [0xEF, 0xBE, 0xAD, 0xDE, 0x78, 0x56, 0x34, 0x12];
+        mock::with_host(|h| h.process(1).map(0x100, bytes));
+        let p = process(1);
+        assert_eq!(p.read_pointer(0x100, PointerWidth::Bits32), Ok(0xDEADBEEF));
+        assert_eq!(
+            p.read_pointer(0x100, PointerWidth::Bits64),
+            Ok(0x12345678DEADBEEF)
+        );
+        assert_eq!(
+            p.read_pointer(0x104, PointerWidth::Bits64),
+            Err(Error::FailedRead {
+                address: 0x104,
+                len: 8
+            })
+        );
+    }
+
+    #[test]
+    fn read_many() {
+        mock::with_host(|h| {
+            let p = h.process(1);
+            p.map(0x100, (0..0x40).collect::<Vec<u8>>());
+            p.map(0x5000, [9; 4]);
+        });
+        let p = process(1);
+        let (mut a, mut b, mut c, mut d) = ([0; 2], [0; 4], [0; 1], [0; 4]);
+        let results = p.read_many(&mut [
+            (0x130, &mut b),
+            (0x5000, &mut d),
+            (0x100, &mut a),
+            (0x13F, &mut c),
+        ]);
+        assert_eq!(results, [Ok(()), Ok(()), Ok(()), Ok(())]);
+        assert_eq!(
+            (a, b, c, d),
+            ([0, 1], [0x30, 0x31, 0x32, 0x33], [0x3F], [9; 4])
+        );
+        mock::with_host(|h| assert_eq!(h.reads, 2));
+
+        // the group fails as a whole, so each request gets read on its own
+        let results = p.read_many(&mut [(0x100, &mut a), (0x13E, &mut d)]);
+        assert_eq!(
+            results,
+            [
+                Ok(()),
+                Err(Error::FailedRead {
+                    address: 0x13E,
+                    len: 4
+                })
+            ]
+        );
+    }
+
+    #[test]
+    fn read_partial() {
+        mock::with_host(|h| h.process(1).map(0x100, [1, 2, 3, 4, 5, 6, 7, 8]));
+        let p = process(1);
+        let mut buf = [0; 16];
+        assert_eq!(p.read_partial(0x100, &mut buf), Ok(8));
+        assert_eq!(buf[..9], [1, 2, 3, 4, 5, 6, 7, 8, 0]);
+        assert_eq!(p.read_partial(0x105, &mut buf[..2]), Ok(2));
+        assert_eq!(
+            p.read_partial(0x108, &mut buf),
+            Err(Error::FailedRead {
+                address: 0x108,
+                len: 16
+            })
+        );
+        assert_eq!(
+            p.read_into_buf(0x100, &mut buf),
+            Err(Error::FailedRead {
+                address: 0x100,
+                len: 16
+            })
+        );
+    }
+
+    #[test]
+    fn read_at_offsets() {
+        mock::with_host(|h| {
+            let p = h.process(1);
+            p.map(0x100, 0x200u64.to_le_bytes());
+            p.map(0x210, 0x300u32.to_le_bytes());
+            p.map(0x2F8, 7u16.to_le_bytes());
+        });
+        let mut p = process(1);
+        assert_eq!(p.read_at_offsets::<u16>(0x2F8, &[]), Ok(7));
+        assert_eq!(p.read_at_offsets::<u32>(0x100, &[0x10]), Ok(0x300));
+        assert_eq!(
+            p.read_at_offsets::<u16>(0x100, &[0x10, -0x8]),
+            Err(Error::FailedRead {
+                address: 0x210,
+                len: 8
+            })
+        );
+        p.set_pointer_width(PointerWidth::Bits32);
+        assert_eq!(p.read_at_offsets::<u16>(0x100, &[0x10, -0x8]), Ok(7));
+    }
+
+    #[test]
+    fn read_through() {
+        mock::with_host(|h| {
+            let p = h.process(1);
+            p.map(0x100, 0x200u64.to_le_bytes());
+            p.map(0x108, 0u64.to_le_bytes());
+            p.map(0x200, [1u32, 2, 3].map(u32::to_le_bytes).concat());
+        });
+        let p = process(1);
+        assert_eq!(p.read_through::<u32>(0x100, 8, PointerWidth::Bits64), Ok(3));
+        assert_eq!(p.read_through::<u32>(0x100, 4, PointerWidth::Bits32), Ok(2));
+        assert_eq!(
+            p.read_through::<u32>(0x108, 4, PointerWidth::Bits64),
+            Err(Error::NullPointer(0x108))
+        );
+    }
+
+    #[test]
+    fn invalid_intermediate_pointers() {
+        mock::with_host(|h| {
+            let p = h.process(1);
+            p.map(0x100, 0u64.to_le_bytes());
+            p.map(0x200, 0xFFFF_FFF0u64.to_le_bytes());
+        });
+        let mut p = process(1);
+        assert!(!p.is_likely_valid(0));
+        assert!(p.is_likely_valid(0x1_0000_0000));
+        assert_eq!(
+            p.read_at_offsets::<u8>(0x100, &[0x10, 0x8]),
+            Err(Error::NullPointer(0x100))
+        );
+        assert_eq!(
+            p.read_at_offsets::<u8>(0x200, &[0x10, 0x8]),
+            Err(Error::FailedRead {
+                address: 0x1_0000_0000,
+                len: 8
+            })
+        );
+        p.set_pointer_width(PointerWidth::Bits32);
+        assert!(!p.is_likely_valid(0x1_0000_0000));
+        assert_eq!(
+            p.read_at_offsets::<u8>(0x200, &[0x10, 0x8]),
+            Err(Error::InvalidPointer(0x1_0000_0000))
+        );
+    }
+
+    #[test]
+    fn read_pointer_path() {
+        mock::with_host(|h| {
+            let p = h.process(1);
+            p.map(0x100, 0x200u64.to_le_bytes());
+            p.map(0x210, 0x300u64.to_le_bytes());
+            p.map(0x348, 42u32.to_le_bytes());
+            p.map(0x400, 0u64.to_le_bytes());
+        });
+        let p = process(1);
+        assert_eq!(p.read_pointer_path::<u32>(0x100, &[0x10, 0x48]), Ok(42));
+        assert_eq!(p.read_pointer_path::<u32>(0x348, &[]), Ok(42));
+        assert_eq!(
+            p.read_pointer_path::<u32>(0x100, &[0x10, 0x48, 0]),
+            Err(Error::FailedRead {
+                address: 0x348,
+                len: 8
+            })
+        );
+        assert_eq!(
+            p.read_pointer_path::<u32>(0x400, &[0x10]),
+            Err(Error::NullPointer(0x400))
+        );
+    }
+
+    #[test]
+    fn read_be() {
+        let mut bytes = vec![0x12, 0x34, 0x56, 0x78];
+        bytes.extend(1.5f32.to_be_bytes());
+        bytes.extend([1u16, 2].map(u16::to_be_bytes).concat());
+        mock::with_host(|h| h.process(1).map(0x100, bytes));
+        let mut p = process(1);
+        assert_eq!(p.read_be::<u32>(0x100), Ok(0x12345678));
+        assert_eq!(p.read_le::<u32>(0x100), Ok(0x78563412));
+        assert_eq!(p.read_be::<i16>(0x102), Ok(0x5678));
+        assert_eq!(p.read_be::<f32>(0x104), Ok(1.5));
+        assert_eq!(p.read_be::<[u16; 2]>(0x108), Ok([1, 2]));
+        assert_eq!(p.read_endian::<u32>(0x100), Ok(0x78563412));
+        p.set_endian(Endian::Big);
+        assert_eq!(p.read_endian::<u32>(0x100), Ok(0x12345678));
+    }
+
+    #[test]
+    fn big_endian_pointers() {
+        mock::with_host(|h| {
+            let p = h.process(1);
+            p.map(0x8000_0100, 0x8000_0200u32.to_be_bytes());
+            p.map(0x8000_0210, 0x8000_0300u32.to_be_bytes());
+            p.map(0x8000_0304, 7u16.to_le_bytes());
+        });
+        let mut p = process(1);
+        assert_eq!(
+            p.read_be_pointer(0x8000_0100, PointerWidth::Bits32),
+            Ok(0x8000_0200)
+        );
+        assert_eq!(
+            p.read_le_pointer(0x8000_0100, PointerWidth::Bits32),
+            Ok(0x0002_0080)
+        );
+        p.set_pointer_width(PointerWidth::Bits32);
+        p.set_endian(Endian::Big);
+        assert_eq!(p.read_at_offsets::<u16>(0x8000_0100, &[0x10, 0x4]), Ok(7));
+    }
+
+    #[test]
+    fn equal_handles() {
+        mock::with_host(|h| {
+            h.spawn(3, "game.exe");
+            h.spawn(4, "other.exe");
+        });
+        let a = Process::attach("game.exe").unwrap();
+        // only `a` owns the handle, so it's only detached once
+        let shared = mem::ManuallyDrop::new(Process::new(a.handle(), String::new()));
+        assert_eq!(a, *shared);
+        // attaching again gets a handle of its own
+        let b = Process::attach("game.exe").unwrap();
+        assert_ne!(a, b);
+        assert_ne!(a, Process::attach("other.exe").unwrap());
+        let handles = [a.handle(), b.handle()];
+        drop((a, b));
+        mock::with_host(|h| {
+            assert_eq!(h.detaches.iter().filter(|&&d| d == handles[0]).count(), 1);
+            assert!(h.detaches.contains(&handles[1]));
+        });
+    }
+
+    #[test]
+    fn short_reads_fail() {
+        mock::with_host(|h| h.process(1).map(0xFFC, [1, 2, 3, 4]));
+        let p = process(1);
+        assert_eq!(p.read::<u32>(0xFFC), Ok(0x0403_0201));
+        // the last 4 bytes aren't mapped, so none of it counts as read
+        assert_eq!(
+            p.read::<u64>(0xFFC),
+            Err(Error::FailedRead {
+                address: 0xFFC,
+                len: 8
+            })
+        );
+        let mut buf = [0; 8];
+        assert_eq!(
+            p.read_into_buf(0xFFC, &mut buf),
+            Err(Error::FailedRead {
+                address: 0xFFC,
+                len: 8
+            })
+        );
+        assert_eq!(p.read_partial(0xFFC, &mut buf), Ok(4));
+    }
+
+    #[test]
+    fn read_fallbacks() {
+        mock::with_host(|h| h.process(1).map(0x100, 5u32.to_le_bytes()));
+        let p = process(1);
+        assert_eq!(p.read_or(0x100, 7u32), 5);
+        assert_eq!(p.read_or(0x200, 7u32), 7);
+        assert_eq!(p.read_opt::<u32>(0x100), Some(5));
+        assert_eq!(p.read_opt::<u32>(0x102), None);
+    }
+
+    #[test]
+    fn read_checked_floats() {
+        mock::with_host(|h| {
+            let p = h.process(1);
+            p.map(
+                0x100,
+                [1.5f32, f32::NAN, f32::NEG_INFINITY]
+                    .map(f32::to_le_bytes)
+                    .concat(),
+            );
+            p.map(0x200, [2.5f64, f64::NAN].map(f64::to_le_bytes).concat());
+        });
+        let p = process(1);
+        assert_eq!(p.read_f32_checked(0x100), Ok(1.5));
+        assert_eq!(p.read_f32_checked(0x104), Err(Error::InvalidFloat));
+        assert_eq!(p.read_f32_checked(0x108), Err(Error::InvalidFloat));
+        assert_eq!(p.read_f64_checked(0x200), Ok(2.5));
+        assert_eq!(p.read_f64_checked(0x208), Err(Error::InvalidFloat));
+    }
+
+    #[test]
+    fn read_cstr_n() {
+        let mut memory = vec![b'a'; 0x400];
+        memory[10] = 0;
+        mock::with_host(|h| h.process(1).map(0x100, memory));
+        let p = process(1);
+        assert_eq!(p.read_cstr_n::<16>(0x100).as_deref(), Ok("aaaaaaaaaa"));
+        assert_eq!(
+            p.read_cstr_n::<16>(0x10B).as_deref(),
+            Ok("a".repeat(15).as_str())
+        );
+        assert_eq!(p.read_cstr_n::<512>(0x10B), Ok("a".repeat(511)));
+        assert_eq!(
+            p.read_cstr_n::<512>(0x400),
+            Err(Error::FailedRead {
+                address: 0x400,
+                len: 511
+            })
+        );
+        assert_eq!(p.read_cstr(0x100).as_deref(), Ok("aaaaaaaaaa"));
+    }
+
+    #[test]
+    fn write() {
+        mock::with_host(|h| {
+            let p = h.process(1);
+            p.map(0x100, [0; 8]);
+            p.map(0x200, [0; 4]);
+            p.protect(0x200, 0b10);
+        });
+        let p = process(1);
+        assert_eq!(p.write(0x104, 7u32), Ok(()));
+        assert_eq!(p.read::<u32>(0x104), Ok(7));
+        assert_eq!(
+            p.write_into_buf(0x106, &[1, 2, 3]),
+            Err(Error::FailedWrite {
+                address: 0x106,
+                len: 3
+            })
+        );
+        // read only
+        assert!(p.write(0x200, 1u8).is_err());
+        assert_eq!(p.read::<u8>(0x200), Ok(0));
+        mock::with_host(|h| h.process(1).closed = true);
+        assert_eq!(p.write(0x100, 1u8), Err(Error::ProcessNotOpen));
+    }
+
+    #[test]
+    fn read_cstr_into() {
+        mock::with_host(|h| {
+            h.process(1)
+                .map(0x100, *b"Celeste\0Farewell\xFF\0caf\xC3\xA9\0")
+        });
+        let p = process(1);
+        let mut buf = [0; 16];
+        assert_eq!(p.read_cstr_into(0x100, &mut buf), Ok("Celeste"));
+        assert_eq!(p.read_cstr_into(0x100, &mut buf[..4]), Ok("Cele"));
+        assert_eq!(
+            p.read_cstr_into(0x108, &mut buf),
+            Err(Error::InvalidString(0x108))
+        );
+        // the é doesn't fit
+        assert_eq!(p.read_cstr_into(0x112, &mut buf[..4]), Ok("caf"));
+        assert_eq!(p.read_cstr_into(0x114, &mut buf), Ok("f\u{e9}"));
+        // runs into unmapped memory before finding the null
+        mock::with_host(|h| h.process(1).map(0x200, *b"abc"));
+        assert_eq!(p.read_cstr_into(0x200, &mut buf), Ok("abc"));
+    }
+
+    #[test]
+    fn read_array() {
+        mock::with_host(|h| h.process(1).map(0x100, [1, 0, 2, 0, 3, 0]));
+        let p = process(1);
+        assert_eq!(p.read_array::<u16, 3>(0x100), Ok([1, 2, 3]));
+        assert_eq!(
+            p.read_array::<u16, 4>(0x100),
+            Err(Error::FailedRead {
+                address: 0x100,
+                len: 8
+            })
+        );
+    }
+
+    #[test]
+    fn try_read_cstr() {
+        mock::with_host(|h| h.process(1).map(0x100, *b"Celeste\0Farewell\xFF\0"));
+        let p = process(1);
+        assert_eq!(p.try_read_cstr(0x100, 7).as_deref(), Ok("Celeste"));
+        assert_eq!(p.try_read_cstr(0x100, 6), Err(Error::InvalidString(0x100)));
+        assert_eq!(p.try_read_cstr(0x108, 64), Err(Error::InvalidString(0x108)));
+        assert_eq!(p.try_read_cstr(0x111, 64).as_deref(), Ok(""));
+        assert_eq!(
+            p.try_read_cstr(0x200, 64),
+            Err(Error::FailedRead {
+                address: 0x200,
+                len: 65
+            })
+        );
+    }
+
+    #[test]
+    fn read_wstr() {
+        let mut memory: Vec<u8> = "Château\0"
+            .encode_utf16()
+            .flat_map(u16::to_le_bytes)
+            .collect();
+        // an unpaired surrogate
+        memory.extend([0x00, 0xD8, b'!', 0, 0, 0]);
+        memory.resize(0x400, b'a');
+        mock::with_host(|h| h.process(1).map(0x100, memory));
+        let p = process(1);
+        assert_eq!(p.read_wstr(0x100).as_deref(), Ok("Château"));
+        assert_eq!(p.read_wstr(0x110).as_deref(), Ok("\u{FFFD}!"));
+        assert_eq!(p.read_wstr_n::<4>(0x100).as_deref(), Ok("Châ"));
+        assert_eq!(
+            p.read_wstr(0x3F0),
+            Err(Error::FailedRead {
+                address: 0x3F0,
+                len: 510
+            })
+        );
+    }
+
+    #[test]
+    fn read_mono_string() {
+        let utf16 = |s: &str| -> Vec<u8> { s.encode_utf16().flat_map(u16::to_le_bytes).collect() };
+        mock::with_host(|h| {
+            let p = h.process(1);
+            let mut object = vec![0; 0x10];
+            object.extend(7i32.to_le_bytes());
+            object.extend(utf16("Céleste"));
+            p.map(0x100, object);
+            let mut object = vec![0; 0x8];
+            object.extend(2i32.to_le_bytes());
+            object.extend(utf16("🍓"));
+            p.map(0x200, object);
+            let mut object = vec![0; 0x10];
+            object.extend((-1i32).to_le_bytes());
+            p.map(0x300, object);
+        });
+        let mut p = process(1);
+        assert_eq!(p.read_mono_string(0x100).as_deref(), Ok("Céleste"));
+        assert_eq!(p.read_mono_string(0x300), Err(Error::InvalidString(0x300)));
+        assert_eq!(p.read_mono_string(0), Err(Error::InvalidPointer(0)));
+        p.set_pointer_width(PointerWidth::Bits32);
+        assert_eq!(p.read_mono_string(0x200).as_deref(), Ok("🍓"));
+        let layout = MonoStringLayout {
+            length: 0x10,
+            chars: 0x14,
+        };
+        assert_eq!(
+            p.read_mono_string_with(0x100, layout).as_deref(),
+            Ok("Céleste")
+        );
+    }
+
+    #[test]
+    fn read_fixed_string() {
+        mock::with_host(|h| h.process(1).map(0x100, *b"Madeline\0\0\0\0Granny\0\0"));
+        let p = process(1);
+        assert_eq!(p.read_fixed_string(0x100, 12).as_deref(), Ok("Madeline"));
+        assert_eq!(p.read_fixed_string(0x10C, 6).as_deref(), Ok("Granny"));
+        assert_eq!(p.read_fixed_string(0x10C, 8).as_deref(), Ok("Granny"));
+        assert_eq!(
+            p.read_fixed_string(0x10C, 16),
+            Err(Error::FailedRead {
+                address: 0x10C,
+                len: 16
+            })
+        );
+    }
+
+    #[test]
+    fn read_len_prefixed_string() {
+        mock::with_host(|h| {
+            let p = h.process(1);
+            p.map(0x100, *b"\x07Celeste");
+            p.map(0x200, [&8u32.to_le_bytes()[..], b"Madeline"].concat());
+            p.map(0x300, [&5000u32.to_le_bytes()[..], &[b'a'; 5000]].concat());
+        });
+        let p = process(1);
+        assert_eq!(
+            p.read_len_prefixed_string(0x100, 1).as_deref(),
+            Ok("Celeste")
+        );
+        assert_eq!(
+            p.read_len_prefixed_string(0x200, 4).as_deref(),
+            Ok("Madeline")
+        );
+        assert_eq!(
+            p.read_len_prefixed_string(0x300, 4),
+            Err(Error::InvalidString(0x300))
+        );
+        assert_eq!(
+            p.read_len_prefixed_string_with_max(0x300, 4, 5000)
+                .map(|s| s.len()),
+            Ok(5000)
+        );
+    }
+
+    #[test]
+    fn read_array_via_count() {
+        mock::with_host(|h| {
+            let p = h.process(1);
+            p.map(0x100, [3u32, 0, 0x200, 0].map(u32::to_le_bytes).concat());
+            p.map(0x200, [1u16, 2, 3].map(u16::to_le_bytes).concat());
+            p.map(0x300, [0x10001u32, 0].map(u32::to_le_bytes).concat());
+        });
+        let p = process(1);
+        assert_eq!(
+            p.read_array_via_count::<u16>(0x100, 0x108, PointerWidth::Bits64),
+            Ok(vec![1, 2, 3])
+        );
+        assert_eq!(
+            p.read_array_via_count::<u32>(0x100, 0x108, PointerWidth::Bits64),
+            Err(Error::FailedRead {
+                address: 0x200,
+                len: 12
+            })
+        );
+        assert_eq!(
+            p.read_array_via_count::<u16>(0x104, 0x10C, PointerWidth::Bits32),
+            Ok(vec![])
+        );
+        assert_eq!(
+            p.read_array_via_count::<u16>(0x300, 0x108, PointerWidth::Bits64),
+            Err(Error::InvalidCount(0x10001))
+        );
+    }
+
+    #[test]
+    fn read_chunks() {
+        let memory: Vec<u8> = (0..30).collect();
+        mock::with_host(|h| h.process(1).map(0x100, memory.clone()));
+        let p = process(1);
+        let chunks: Vec<_> = p.read_chunks(0x100, 30, 8).collect::<Result<_>>().unwrap();
+        assert_eq!(
+            chunks.iter().map(Vec::len).collect::<Vec<_>>(),
+            [8, 8, 8, 6]
+        );
+        assert_eq!(chunks.concat(), memory);
+
+        let mut chunks = p.read_chunks(0x110, 32, 8);
+        assert_eq!(chunks.next(), Some(Ok(memory[0x10..0x18].to_vec())));
+        assert_eq!(
+            chunks.next(),
+            Some(Err(Error::FailedRead {
+                address: 0x118,
+                len: 8
+            }))
+        );
+        assert_eq!(chunks.next(), None);
+    }
+
+    #[test]
+    fn read_string_table() {
+        mock::with_host(|h| {
+            let p = h.process(1);
+            p.map(
+                0x100,
+                [0x200u32, 0x210, 0x220].map(u32::to_le_bytes).concat(),
+            );
+            // `read_cstr` always reads 255 bytes so leave some room after the strings
+            let mut strings = vec![0; 0x200];
+            strings[..8].copy_from_slice(b"Prologue");
+            strings[0x10..0x1D].copy_from_slice(b"Forsaken City");
+            p.map(0x200, strings);
+        });
+        let p = process(1);
+        assert_eq!(
+            p.read_string_table(0x100, 3, PointerWidth::Bits32),
+            Ok(vec!["Prologue".into(), "Forsaken City".into(), "".into()])
+        );
+        assert_eq!(
+            p.read_string_table(0x100, 4, PointerWidth::Bits32),
+            Err(Error::FailedRead {
+                address: 0x10C,
+                len: 4
+            })
+        );
+    }
+
+    #[test]
+    fn dump() {
+        let bytes = [&b"Hello, world!\0\x01\xFF".repeat(2), &b"Hell"[..]].concat();
+        mock::with_host(|h| h.process(1).map(0xABC0, bytes));
+        assert_eq!(
+            process(1).dump(0xABC0, 36).unwrap(),
+            "000000000000abc0  48 65 6c 6c 6f 2c 20 77 6f 72 6c 64 21 00 01 ff  |Hello, world!...|\n\
+             000000000000abd0  48 65 6c 6c 6f 2c 20 77 6f 72 6c 64 21 00 01 ff  |Hello, world!...|\n\
+             000000000000abe0  48 65 6c 6c                                      |Hell|\n"
+        );
+        assert_eq!(
+            process(1).dump(0xABC0, 48),
+            Err(Error::FailedRead {
+                address: 0xABC0,
+                len: 48
+            })
+        );
+    }
+
+    #[test]
+    fn detach_once() {
+        process(1).detach();
+        drop(process(2));
+        mock::with_host(|h| assert_eq!(h.detaches, [1, 2]));
+    }
+
+    #[test]
+    fn game_process_reattaches() {
+        mock::with_host(|h| h.spawn(1, "Game.exe").map(0x10, [1]));
+        let mut game = GameProcess::new("Game.exe");
+        crate::__begin_tick();
+        assert_eq!(game.read::<u8>(0x10), Ok(1));
+
+        // the game closes and comes back with a new handle
+        mock::with_host(|h| h.process(1).closed = true);
+        assert_eq!(game.read::<u8>(0x10), Err(Error::ProcessNotOpen));
+        mock::with_host(|h| h.spawn(2, "Game.exe").map(0x10, [2]));
+        assert_eq!(game.read::<u8>(0x10), Err(Error::ProcessNotOpen));
+        crate::__begin_tick();
+        assert_eq!(game.read::<u8>(0x10), Ok(2));
+        assert_eq!(game.get().map(|p| p.handle), Some(2));
+        assert!(game.just_attached());
+        crate::__begin_tick();
+        assert!(!game.just_attached());
+    }
+
+    #[test]
+    fn game_process_retry_interval() {
+        let mut game = GameProcess::new("Game.exe").with_retry_interval(3);
+        crate::__begin_tick();
+        assert!(game.get().is_none());
+        mock::with_host(|h| {
+            h.spawn(1, "Game.exe");
+        });
+        for _ in 0..2 {
+            crate::__begin_tick();
+            assert!(game.get().is_none());
+        }
+        crate::__begin_tick();
+        assert!(game.just_attached());
+
+        // a game that closes is retried right away
+        mock::with_host(|h| {
+            h.process(1).closed = true;
+            h.spawn(2, "Game.exe");
+        });
+        crate::__begin_tick();
+        assert_eq!(game.get().map(Process::handle), Some(2));
+    }
+
+    #[test]
+    fn game_process_waits_for_module() {
+        let mut game = GameProcess::attach_and_wait_for_module("Game.exe", "game.dll");
+        mock::with_host(|h| h.spawn(1, "Game.exe").map(0x10, [1]));
+        crate::__begin_tick();
+        assert!(game.get().is_none());
+        assert_eq!(game.read::<u8>(0x10), Err(Error::ProcessNotOpen));
+        mock::with_host(|h| h.process(1).load("game.dll", 0x1000, [0; 0x10]));
+        crate::__begin_tick();
+        assert!(game.just_attached());
+        assert_eq!(game.read::<u8>(0x10), Ok(1));
+        assert_eq!(
+            game.module(),
+            Some(Module {
+                base: 0x1000,
+                size: 0x10
+            })
+        );
+        crate::__begin_tick();
+        assert!(!game.just_attached());
+
+        // a restarted game has to load the module again
+        mock::with_host(|h| {
+            h.process(1).closed = true;
+            h.spawn(2, "Game.exe");
+        });
+        crate::__begin_tick();
+        assert!(game.get().is_none());
+        assert_eq!(game.module(), None);
+    }
+
+    #[test]
+    fn once_process_caches() {
+        mock::with_host(|h| {
+            h.spawn(1, "Game.exe");
+        });
+        let mut game = OnceProcess::new();
+        assert_eq!(game.get(), None);
+        for _ in 0..3 {
+            crate::__begin_tick();
+            assert_eq!(
+                game.get_or_attach(&Host, "Game.exe").map(|p| p.handle()),
+                Some(1)
+            );
         }
+        mock::with_host(|h| assert_eq!(h.attaches, 1));
+
+        mock::with_host(|h| {
+            h.process(1).closed = true;
+            h.spawn(2, "Game.exe");
+        });
+        assert_eq!(
+            game.get_or_attach(&Host, "Game.exe").map(|p| p.handle()),
+            Some(2)
+        );
+        mock::with_host(|h| assert_eq!(h.attaches, 2));
+    }
+
+    #[test]
+    fn module_watcher_caches() {
+        mock::with_host(|h| {
+            h.spawn(1, "Game.exe");
+        });
+        let p = Process::attach("Game.exe").unwrap();
+        let mut unity = ModuleWatcher::new("UnityPlayer.dll");
+        for tick in 1..=5 {
+            crate::__begin_tick();
+            if tick == 3 {
+                mock::with_host(|h| h.process(1).load("UnityPlayer.dll", 0x1000, [0; 4]));
+            }
+            let expected = (tick >= 3).then_some(0x1000);
+            assert_eq!(unity.get(&p), expected);
+        }
+        assert_eq!(unity.resolved_tick(), Some(crate::current_tick() - 2));
+        mock::with_host(|h| assert_eq!(h.module_lookups, 3));
+    }
+
+    #[test]
+    fn main_module() {
+        mock::with_host(|h| h.spawn(1, "Game.exe").load("Game.exe", 0x400000, [0; 16]));
+        let p = Process::attach("Game.exe").unwrap();
+        assert_eq!(p.name(), "Game.exe");
+        assert_eq!(p.main_module(), Some(0x400000));
+    }
+
+    #[test]
+    fn module_sizes() {
+        mock::with_host(|h| h.process(1).load("mono.dll", 0x8000, [0; 0x300]));
+        let p = process(1);
+        assert_eq!(p.module_size("mono.dll"), Some(0x300));
+        assert_eq!(p.module_size("UnityPlayer.dll"), None);
+        assert_eq!(
+            p.first_module(&["mono-2.0-bdwgc.dll", "mono.dll"]),
+            Some((
+                "mono.dll",
+                Module {
+                    base: 0x8000,
+                    size: 0x300
+                }
+            ))
+        );
+        assert_eq!(p.first_module(&["mono-2.0-bdwgc.dll"]), None);
+    }
+
+    #[test]
+    fn path() {
+        let path = "C:\\Program Files\\Celeste\\Celeste.exe";
+        mock::with_host(|h| {
+            h.spawn(1, "Celeste.exe").path = Some(path.into());
+            h.spawn(2, "Other.exe");
+        });
+        assert_eq!(process(1).path().as_deref(), Some(path));
+        assert_eq!(process(2).path(), None);
+    }
+
+    #[test]
+    fn find_string() {
+        let mut memory = vec![0; 8192];
+        memory[4093..4100].copy_from_slice(b"Celeste");
+        mock::with_host(|h| h.process(1).load("game.dll", 0x10000, memory));
+        let p = process(1);
+        let module = p.module_range("game.dll").unwrap();
+        assert_eq!(
+            module,
+            Module {
+                base: 0x10000,
+                size: 8192
+            }
+        );
+        assert_eq!(p.find_string(&module, "Celeste"), Some(0x10000 + 4093));
+        assert_eq!(p.find_string(&module, "Madeline"), None);
+    }
+
+    #[test]
+    fn memory_ranges() {
+        mock::with_host(|h| {
+            let p = h.process(1);
+            p.map(0x1000, [0; 0x100]);
+            p.map(0x8000, *b"\0\0\x48\x8B\x05");
+            p.map(0x9000, [0x48, 0x8B, 0x05]);
+            p.protect(0x9000, MemoryRangeFlags::EXECUTE.0);
+        });
+        let p = process(1);
+        let ranges = p.memory_ranges();
+        assert_eq!(ranges.len(), 2);
+        assert_eq!((ranges[0].base, ranges[0].size), (0x1000, 0x100));
+        assert!(ranges[0].flags.contains(MemoryRangeFlags::READ));
+        assert!(!ranges[0].flags.contains(MemoryRangeFlags::EXECUTE));
+        assert!(ranges[1].contains(0x8004));
+        assert!(!ranges[1].contains(0x8005));
+        assert_eq!(p.scan_memory("48 8B ?? "), Some(0x8002));
+        assert_eq!(p.scan_memory("48 8B 06"), None);
+    }
+
+    #[test]
+    fn scan_signature() {
+        let mut memory = vec![0; 8192];
+        memory[4094..4100].copy_from_slice(&[0x48, 0x8B, 0x12, 0x34, 0x89, 0x05]);
+        mock::with_host(|h| h.process(1).load("game.dll", 0x10000, memory));
+        let p = process(1);
+        assert_eq!(
+            p.scan_signature("game.dll", "48 8B ?? ?? 89 05"),
+            Some(0x10000 + 4094)
+        );
+        assert_eq!(
+            p.scan_signature("game.dll", "48 8b ? 34"),
+            Some(0x10000 + 4094)
+        );
+        assert_eq!(p.scan_signature("game.dll", "48 8B ?? ?? 89 06"), None);
+        assert_eq!(p.scan_signature("other.dll", "48"), None);
+        assert_eq!(parse_signature("0F ?? a0"), [Some(0x0F), None, Some(0xA0)]);
+    }
+
+    #[test]
+    fn pe_export() {
+        let mut dll = mock::Memory::new(0x400);
+        dll.pe_export("update", 0x380);
+        mock::with_host(|h| h.process(1).load("game.dll", 0x10000, dll.0));
+        let p = process(1);
+        assert_eq!(p.pe_export(0x10000, "update"), Some(0x10380));
+        assert_eq!(p.pe_export(0x10000, "updat"), None);
+        assert_eq!(p.pe_export(0x20000, "update"), None);
+    }
+
+    #[test]
+    fn find_pointers_to() {
+        let target = 0x1234_5678_9ABCu64;
+        let mut memory = vec![0; 8192];
+        memory[0x10..0x18].copy_from_slice(&target.to_le_bytes());
+        // straddles the boundary between the first two chunks
+        memory[4093..4101].copy_from_slice(&target.to_le_bytes());
+        mock::with_host(|h| h.process(1).load("game.dll", 0x10000, memory));
+        let p = process(1);
+        let module = p.module_range("game.dll").unwrap();
+        assert_eq!(
+            p.find_pointers_to(&module, target, PointerWidth::Bits64),
+            [0x10010]
+        );
+        assert_eq!(
+            p.find_pointers_to_aligned(&module, target, PointerWidth::Bits64, 1),
+            [0x10010, 0x10000 + 4093]
+        );
+        assert_eq!(
+            p.find_pointers_to(&module, target, PointerWidth::Bits32),
+            []
+        );
     }
 }
@@ -0,0 +1,56 @@
+//! Information about the autosplitter runtime that's hosting the splitter.
+//!
+//! Not every runtime provides every host function, and a wasm module that
+//! imports a function the host doesn't have will fail to load entirely. So the
+//! newer host functions are opt-in through cargo features, and the runtime has
+//! no function for asking what it supports or which version it is.
+
+/// A group of host functions that only some runtimes provide, each behind the
+/// cargo feature of the same name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Capability {
+    /// Settings the runner can change, see the `settings` feature.
+    Settings,
+    /// Skipping and undoing splits, see the `skip-undo-split` feature.
+    SkipUndoSplit,
+    /// Asking which split the run is on, see the `split-index` feature.
+    SplitIndex,
+    /// Asking for a process's path, see the `process-path` feature.
+    ProcessPath,
+    /// Listing a process's memory ranges, see the `memory-ranges` feature.
+    MemoryRanges,
+    /// Attaching by PID and listing processes, see the `process-list` feature.
+    ProcessList,
+    /// Writing to a process's memory, see the `process-write` feature.
+    ProcessWrite,
+}
+
+/// Whether the splitter can use `capability`. The runtime can't be asked, so
+/// this is decided by the cargo features the splitter was built with: a
+/// splitter that imports a host function only loads on runtimes that provide
+/// it, so once it's running every enabled feature is usable.
+pub fn has_capability(capability: Capability) -> bool {
+    match capability {
+        Capability::Settings => cfg!(any(test, feature = "settings")),
+        Capability::SkipUndoSplit => cfg!(any(test, feature = "skip-undo-split")),
+        Capability::SplitIndex => cfg!(any(test, feature = "split-index")),
+        Capability::ProcessPath => cfg!(any(test, feature = "process-path")),
+        Capability::MemoryRanges => cfg!(any(test, feature = "memory-ranges")),
+        Capability::ProcessList => cfg!(any(test, feature = "process-list")),
+        Capability::ProcessWrite => cfg!(any(test, feature = "process-write")),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{has_capability, Capability};
+
+    #[test]
+    fn capabilities_follow_the_mocked_host() {
+        // the crate's own tests run against a host that has everything
+        assert!(has_capability(Capability::Settings));
+        assert!(has_capability(Capability::SkipUndoSplit));
+        assert!(has_capability(Capability::ProcessWrite));
+    }
+}
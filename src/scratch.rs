@@ -0,0 +1,95 @@
+use alloc::vec::Vec;
+
+use crate::{Address, Error, Pod, Process, Result};
+
+/// A reusable buffer for reading variable length data every tick without
+/// allocating each time. The buffer only grows when a read needs more room
+/// than any read before it.
+#[derive(Debug, Default, Clone)]
+pub struct ScratchBuffer<T = u8> {
+    buf: Vec<T>,
+}
+
+impl<T: Pod> ScratchBuffer<T> {
+    /// Create an empty buffer.
+    pub const fn new() -> Self {
+        Self { buf: Vec::new() }
+    }
+
+    /// Create a buffer with room for `capacity` elements up front.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            buf: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// How many elements the buffer can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.buf.capacity()
+    }
+
+    /// Read `count` contiguous values starting at `addr` into the buffer.
+    pub fn read_vec_into(
+        &mut self,
+        process: &Process,
+        addr: Address,
+        count: usize,
+    ) -> Result<&[T]> {
+        self.buf.clear();
+        self.buf.resize(count, T::zeroed());
+        process.read_into_buf(addr, bytemuck::cast_slice_mut(&mut self.buf))?;
+        Ok(&self.buf)
+    }
+}
+
+impl ScratchBuffer<u8> {
+    /// Read a null terminated string of up to 255 bytes into the buffer.
+    /// Only as much as can be read is searched for the null, so the string
+    /// may end right before unmapped memory. An error is returned if there's
+    /// no null in what could be read or the string isn't valid UTF-8.
+    pub fn read_cstr_into(&mut self, process: &Process, addr: Address) -> Result<&str> {
+        const MAX_STR_LEN: usize = 255;
+        self.buf.resize(MAX_STR_LEN, 0);
+        let read = process.read_partial(addr, &mut self.buf)?;
+        let len = self.buf[..read]
+            .iter()
+            .position(|&b| b == 0)
+            .ok_or(Error::InvalidString(addr))?;
+        core::str::from_utf8(&self.buf[..len]).map_err(|_| Error::InvalidString(addr))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mock;
+
+    #[test]
+    fn reuses_allocation() {
+        let mut bytes = [0; 300];
+        bytes[..5].copy_from_slice(b"hello");
+        bytes[0x100..0x104].copy_from_slice(&[1, 0, 2, 0]);
+        bytes[297..].copy_from_slice(b"hi\0");
+        mock::with_host(|h| h.spawn(1, "Game.exe").map(0x100, bytes));
+        let p = Process::attach("Game.exe").unwrap();
+
+        let mut scratch = ScratchBuffer::new();
+        assert_eq!(scratch.read_cstr_into(&p, 0x100), Ok("hello"));
+        let (ptr, capacity) = (scratch.buf.as_ptr(), scratch.capacity());
+        assert_eq!(scratch.read_cstr_into(&p, 0x101), Ok("ello"));
+        // the string ends right before unmapped memory
+        assert_eq!(scratch.read_cstr_into(&p, 0x100 + 297), Ok("hi"));
+        assert_eq!(scratch.read_vec_into(&p, 0x200, 4), Ok(&[1, 0, 2, 0][..]));
+        assert_eq!((scratch.buf.as_ptr(), scratch.capacity()), (ptr, capacity));
+        assert_eq!(
+            scratch.read_cstr_into(&p, 0x300),
+            Err(Error::FailedRead {
+                address: 0x300,
+                len: 255
+            })
+        );
+
+        let mut words = ScratchBuffer::<u16>::with_capacity(2);
+        assert_eq!(words.read_vec_into(&p, 0x200, 2), Ok(&[1, 2][..]));
+    }
+}
@@ -0,0 +1,113 @@
+//! Options that runners can change from LiveSplit's UI, like whether to split
+//! on every boss or only the last one.
+//!
+//! Settings need host functions that older runtimes don't have, so talking to
+//! the runtime is opt-in through the `settings` feature. Without it settings
+//! can still be declared and read, but they always have their default values.
+
+use alloc::vec::Vec;
+
+/// The splitter's settings. Declare them once when the splitter is created,
+/// then call [`update`](Settings::update) at the start of every tick to pick
+/// up any changes the runner made.
+///
+/// ```no_run
+/// # use livesplit_wrapper::settings::Settings;
+/// let mut settings = Settings::new();
+/// settings.add_bool("bosses", "Split on boss kills", true);
+/// // then every tick
+/// settings.update();
+/// if settings.get("bosses") == Some(true) {
+///     // ...
+/// }
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct Settings {
+    bools: Vec<(&'static str, bool)>,
+}
+
+impl Settings {
+    /// Create an empty set of settings.
+    pub const fn new() -> Self {
+        Self { bools: Vec::new() }
+    }
+
+    /// Add a setting that can be turned on or off, shown to the runner with
+    /// `description`. Keys have to be unique. Returns the setting's current
+    /// value, which can differ from `default` if the runner changed it before.
+    pub fn add_bool(&mut self, key: &'static str, description: &str, default: bool) -> bool {
+        #[cfg(any(test, feature = "settings"))]
+        let value = unsafe {
+            crate::ffi::user_settings_add_bool(
+                key.as_ptr(),
+                key.len(),
+                description.as_ptr(),
+                description.len(),
+                default as u32,
+            ) != 0
+        };
+        #[cfg(not(any(test, feature = "settings")))]
+        let value = {
+            let _ = description;
+            default
+        };
+        self.bools.push((key, value));
+        value
+    }
+
+    /// Get the latest values of every setting from the runtime.
+    pub fn update(&mut self) {
+        #[cfg(any(test, feature = "settings"))]
+        unsafe {
+            use crate::ffi;
+            let map = ffi::settings_map_load();
+            if map == 0 {
+                return;
+            }
+            for (key, value) in &mut self.bools {
+                let setting = ffi::settings_map_get(map, key.as_ptr(), key.len());
+                if setting == 0 {
+                    continue;
+                }
+                let mut new = 0;
+                if ffi::setting_value_get_bool(setting, &mut new) != 0 {
+                    *value = new != 0;
+                }
+                ffi::setting_value_free(setting);
+            }
+            ffi::settings_map_free(map);
+        }
+    }
+
+    /// The value of a setting as of the last update, or `None` if there's no
+    /// setting with that key.
+    pub fn get(&self, key: &str) -> Option<bool> {
+        self.bools
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|&(_, value)| value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mock;
+
+    #[test]
+    fn settings_follow_the_runtime() {
+        let mut settings = Settings::new();
+        assert!(settings.add_bool("bosses", "Split on boss kills", true));
+        assert!(!settings.add_bool("b_sides", "Split on B-Sides", false));
+        mock::with_host(|h| {
+            assert_eq!(h.settings[0].1, "Split on boss kills");
+            h.user_settings.insert("b_sides".into(), true);
+        });
+        assert_eq!(settings.get("b_sides"), Some(false));
+        settings.update();
+        assert_eq!(settings.get("bosses"), Some(true));
+        assert_eq!(settings.get("b_sides"), Some(true));
+        assert_eq!(settings.get("missing"), None);
+        mock::with_host(|h| assert!(h.settings_handles.is_empty()));
+    }
+}
@@ -0,0 +1,232 @@
+#[cfg(doc)]
+use crate::Splitter;
+use crate::{Process, Result};
+
+/// Everything a splitter watches in the game, read all at once every tick,
+/// like the `state` block of an ASL script. Keep it in a [`StateWatcher`] to
+/// have the snapshot from the last tick around as well.
+///
+/// With the `derive` feature this can be derived for structs whose fields are
+/// all [`Pod`](crate::Pod). Each field is read from the address given by its
+/// `#[address]` attribute, which can start with the name of the module the
+/// address is relative to, and can be followed by offsets for a pointer path
+/// like with [`read_at_offsets`](Process::read_at_offsets). Reading fails if
+/// any of the fields can't be read.
+///
+/// ```ignore
+/// use livesplit_wrapper::StateSnapshot;
+///
+/// #[derive(Clone, StateSnapshot)]
+/// struct State {
+///     #[address("Game.exe", 0x1A2B3C)]
+///     level: u32,
+///     #[address("Game.exe", 0x1A2C00, 0x10, 0x48)]
+///     igt: f64,
+///     #[address(0x7FF0_0000)]
+///     loading: u8,
+/// }
+/// ```
+pub trait StateSnapshot: Sized {
+    /// Read every field from the process.
+    fn read(process: &Process) -> Result<Self>;
+}
+
+/// Holds the [`StateSnapshot`] read on the latest tick along with the one
+/// from the tick before, which is what's compared to decide when to start
+/// and split.
+///
+/// Hand it to the runner with [`Splitter::watch_state`] and it's updated
+/// right before every [`update`](Splitter::update), so `old` and `current`
+/// are always ready to compare there:
+///
+/// ```ignore
+/// use livesplit_wrapper::{
+///     GameProcess, HostFunctions, Process, Splitter, StateSnapshot, StateWatcher, WatchState,
+/// };
+///
+/// #[derive(Clone, StateSnapshot)]
+/// struct State {
+///     #[address("Game.exe", 0x1A2B3C)]
+///     level: u32,
+/// }
+///
+/// struct Game {
+///     game: GameProcess,
+///     state: StateWatcher<State>,
+/// }
+///
+/// impl Splitter for Game {
+///     fn new() -> Self {
+///         Self {
+///             game: GameProcess::new("Game.exe"),
+///             state: StateWatcher::new(),
+///         }
+///     }
+///
+///     fn watch_state(&mut self) -> Option<(&mut dyn WatchState, &Process)> {
+///         Some((&mut self.state, self.game.get()?))
+///     }
+///
+///     fn update(&mut self) {
+///         if self.state.changed(|s| s.level) {
+///             self.split();
+///         }
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct StateWatcher<T> {
+    old: Option<T>,
+    current: Option<T>,
+}
+
+impl<T: StateSnapshot + Clone> StateWatcher<T> {
+    /// Create a watcher that hasn't read anything yet.
+    pub const fn new() -> Self {
+        Self {
+            old: None,
+            current: None,
+        }
+    }
+
+    /// Read a new snapshot, moving the current one to
+    /// [`old`](StateWatcher::old). On the first read, or the first one after
+    /// a failed read, both are the new snapshot, so nothing looks like it
+    /// changed. If reading fails both are cleared and this returns `false`.
+    pub fn update(&mut self, process: &Process) -> bool {
+        match T::read(process) {
+            Ok(new) => {
+                self.old = Some(self.current.take().unwrap_or_else(|| new.clone()));
+                self.current = Some(new);
+                true
+            }
+            Err(_) => {
+                self.old = None;
+                self.current = None;
+                false
+            }
+        }
+    }
+
+    /// The snapshot read on the latest update.
+    pub fn current(&self) -> Option<&T> {
+        self.current.as_ref()
+    }
+
+    /// The snapshot read on the update before the latest one.
+    pub fn old(&self) -> Option<&T> {
+        self.old.as_ref()
+    }
+
+    /// Whether the part of the state picked out by `field` is different than
+    /// it was last tick.
+    pub fn changed<U: PartialEq>(&self, field: impl Fn(&T) -> U) -> bool {
+        match (&self.old, &self.current) {
+            (Some(old), Some(current)) => field(old) != field(current),
+            _ => false,
+        }
+    }
+}
+
+/// The part of a [`StateWatcher`] the runner needs to update it, without
+/// knowing what's in the state. See [`Splitter::watch_state`].
+pub trait WatchState {
+    /// Read a new snapshot, see [`StateWatcher::update`].
+    fn update(&mut self, process: &Process) -> bool;
+}
+
+impl<T: StateSnapshot + Clone> WatchState for StateWatcher<T> {
+    fn update(&mut self, process: &Process) -> bool {
+        StateWatcher::update(self, process)
+    }
+}
+
+impl<T: StateSnapshot + Clone> Default for StateWatcher<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing::Harness;
+    use crate::{mock, GameProcess, Splitter};
+    use alloc::vec::Vec;
+
+    #[derive(Debug, Clone, PartialEq, livesplit_wrapper_derive::StateSnapshot)]
+    struct State {
+        #[address("game.dll", 0x10)]
+        level: u32,
+        #[address(0x100, 0x8)]
+        igt: f32,
+    }
+
+    #[test]
+    fn old_and_current() {
+        mock::with_host(|h| {
+            let p = h.spawn(1, "Game.exe");
+            p.load("game.dll", 0x1000, [0; 0x20]);
+            p.map(0x100, 0x200u64.to_le_bytes());
+            p.map(0x208, 1.5f32.to_le_bytes());
+        });
+        let p = Process::attach("Game.exe").unwrap();
+        let mut state = StateWatcher::<State>::new();
+        assert!(state.update(&p));
+        assert_eq!(state.old(), state.current());
+        assert!(!state.changed(|s| s.level));
+
+        mock::with_host(|h| h.process(1).write(0x1010, &3u32.to_le_bytes()));
+        assert!(state.update(&p));
+        assert!(state.changed(|s| s.level) && !state.changed(|s| s.igt));
+        assert_eq!(state.old().unwrap().level, 0);
+        assert_eq!(state.current(), Some(&State { level: 3, igt: 1.5 }));
+
+        mock::with_host(|h| h.process(1).write(0x100, &0u64.to_le_bytes()));
+        assert!(!state.update(&p));
+        assert_eq!(state.current(), None);
+        assert!(!state.changed(|s| s.level));
+    }
+
+    struct Game {
+        game: GameProcess,
+        state: StateWatcher<State>,
+        levels: Vec<u32>,
+    }
+
+    impl Splitter for Game {
+        fn new() -> Self {
+            Self {
+                game: GameProcess::new("Game.exe"),
+                state: StateWatcher::new(),
+                levels: Vec::new(),
+            }
+        }
+
+        fn watch_state(&mut self) -> Option<(&mut dyn WatchState, &Process)> {
+            Some((&mut self.state, self.game.get()?))
+        }
+
+        fn update(&mut self) {
+            if self.state.changed(|s| s.level) {
+                self.levels.push(self.state.current().unwrap().level);
+            }
+        }
+    }
+
+    #[test]
+    fn updated_by_the_runner() {
+        mock::with_host(|h| {
+            let p = h.spawn(1, "Game.exe");
+            p.load("game.dll", 0x1000, [0; 0x20]);
+            p.map(0x100, 0x200u64.to_le_bytes());
+            p.map(0x208, 0f32.to_le_bytes());
+        });
+        let mut splitter = Harness::<Game>::new();
+        for level in [1u32, 1, 2] {
+            mock::with_host(|h| h.process(1).write(0x1010, &level.to_le_bytes()));
+            splitter.update();
+        }
+        assert_eq!(splitter.splitter().levels, [2]);
+    }
+}
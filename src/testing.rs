@@ -0,0 +1,248 @@
+//! A fake runtime for testing splitters natively with `cargo test`, instead of
+//! only being able to try them out inside LiveSplit.
+//!
+//! With the `testing` feature every host function is implemented in memory:
+//! processes are made up of whatever bytes a test maps in, the timer can be
+//! put in any state, and everything the splitter does to the timer or logs is
+//! recorded. Each test thread gets a fresh runtime, so tests can't see each
+//! other's processes or splits. Only enable the feature for tests, since a
+//! splitter built with it won't talk to the real runtime:
+//!
+//! ```toml
+//! [dev-dependencies]
+//! livesplit-wrapper = { version = "0.1", features = ["testing"] }
+//! ```
+//!
+//! ```no_run
+//! use livesplit_wrapper::testing::{self, Harness};
+//! # use livesplit_wrapper::{GameProcess, HostFunctions, Splitter};
+//! # struct MySplitter(GameProcess);
+//! # impl Splitter for MySplitter {
+//! #     fn new() -> Self { MySplitter(GameProcess::new("Game.exe")) }
+//! #     fn update(&mut self) {
+//! #         if self.0.read::<u32>(0x1000) == Ok(1) { self.start() }
+//! #     }
+//! # }
+//!
+//! let game = testing::spawn("Game.exe");
+//! game.map(0x1000, 0u32.to_le_bytes());
+//! let mut splitter = Harness::<MySplitter>::new();
+//! splitter.update();
+//! game.write(0x1000, &1u32.to_le_bytes());
+//! splitter.update();
+//! assert_eq!(testing::state(), livesplit_wrapper::TimerState::Running);
+//! ```
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::time::Duration;
+
+use crate::mock::with_host;
+use crate::{init_logger, Logger, Runner, Splitter, TimerState};
+
+/// Drives a splitter the same way [`register_autosplitter!`] does, one
+/// [`update`](Harness::update) at a time.
+///
+/// [`register_autosplitter!`]: crate::register_autosplitter
+pub struct Harness<S> {
+    runner: Runner<S>,
+}
+
+impl<S: Splitter> Harness<S> {
+    /// Create the splitter with [`Splitter::new`]. Unlike the real runtime
+    /// this doesn't install a panic hook, so failed assertions still get
+    /// printed the way they usually do.
+    pub fn new() -> Self {
+        static LOGGER: Logger = Logger::new(None);
+        init_logger(&LOGGER);
+        Self {
+            runner: Runner::new(),
+        }
+    }
+
+    /// Run one tick of the splitter. Unlike the real runtime panics aren't
+    /// caught, so they fail the test.
+    pub fn update(&mut self) {
+        crate::__begin_tick();
+        self.runner.update();
+    }
+
+    /// The splitter being tested.
+    pub fn splitter(&mut self) -> &mut S {
+        &mut self.runner.splitter
+    }
+}
+
+impl<S: Splitter> Default for Harness<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A fake process, which can be attached to by the name it was
+/// [spawned](spawn) with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FakeProcess {
+    id: u64,
+}
+
+/// Start a fake process with no memory mapped yet.
+pub fn spawn(name: &str) -> FakeProcess {
+    with_host(|h| {
+        let id = h.processes.keys().max().map_or(1, |max| max + 1);
+        h.spawn(id, name);
+        FakeProcess { id }
+    })
+}
+
+impl FakeProcess {
+    /// Make `bytes` readable starting at `addr`.
+    pub fn map(&self, addr: u64, bytes: impl Into<Vec<u8>>) {
+        with_host(|h| h.process(self.id).map(addr, bytes));
+    }
+
+    /// Overwrite memory that was already mapped, which is how the game
+    /// changing a value is simulated.
+    ///
+    /// # Panics
+    ///
+    /// If `addr` isn't mapped.
+    pub fn write(&self, addr: u64, bytes: &[u8]) {
+        with_host(|h| h.process(self.id).write(addr, bytes));
+    }
+
+    /// Load a module called `name` at `base`, with `bytes` as its contents.
+    pub fn load_module(&self, name: &str, base: u64, bytes: impl Into<Vec<u8>>) {
+        with_host(|h| h.process(self.id).load(name, base, bytes));
+    }
+
+    /// Set the path that's reported for the process's executable. Splitters
+    /// only see it with the `process-path` feature.
+    pub fn set_path(&self, path: &str) {
+        with_host(|h| h.process(self.id).path = Some(path.into()));
+    }
+
+    /// Make the process exit, so reads fail and attaching to it again
+    /// doesn't work.
+    pub fn close(&self) {
+        with_host(|h| h.process(self.id).closed = true);
+    }
+
+    /// The process's PID, for attaching to it with
+    /// [`attach_by_pid`](crate::HostFunctions::attach_by_pid).
+    pub fn pid(&self) -> u32 {
+        (self.id + crate::mock::PID_OFFSET) as u32
+    }
+}
+
+/// The timer's state.
+pub fn state() -> TimerState {
+    match with_host(|h| h.state) {
+        1 => TimerState::Running,
+        2 => TimerState::Paused,
+        3 => TimerState::Ended,
+        _ => TimerState::NotRunning,
+    }
+}
+
+/// Put the timer in a state, like the runner starting or finishing a run
+/// by hand.
+pub fn set_state(state: TimerState) {
+    with_host(|h| h.state = state as u32);
+}
+
+/// Which split the run is on, counting from 0.
+pub fn split_index() -> u32 {
+    with_host(|h| h.splits)
+}
+
+/// How many times the run has been reset.
+pub fn resets() -> u32 {
+    with_host(|h| h.resets)
+}
+
+/// The game time that was last set, or `None` if it hasn't been set or was
+/// set to something negative.
+pub fn game_time() -> Option<Duration> {
+    let (secs, nanos) = with_host(|h| h.game_time)?;
+    Some(Duration::new(secs.try_into().ok()?, nanos.try_into().ok()?))
+}
+
+/// Whether game time is paused.
+pub fn is_game_time_paused() -> bool {
+    with_host(|h| h.game_time_paused)
+}
+
+/// The value a variable was last set to.
+pub fn variable(key: &str) -> Option<String> {
+    with_host(|h| {
+        h.variables
+            .iter()
+            .rev()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.clone())
+    })
+}
+
+/// Every message that has been logged.
+pub fn log() -> Vec<String> {
+    with_host(|h| h.log.clone())
+}
+
+/// Change a setting, as if the runner had changed it in LiveSplit.
+pub fn set_setting(key: &str, value: bool) {
+    with_host(|h| h.user_settings.insert(key.into(), value));
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{GameProcess, HostFunctions};
+
+    struct Counter {
+        game: GameProcess,
+    }
+
+    impl Splitter for Counter {
+        fn new() -> Self {
+            Self {
+                game: GameProcess::new("Game.exe"),
+            }
+        }
+
+        fn update(&mut self) {
+            match self.game.read::<u32>(0x1000) {
+                Ok(0) => {}
+                Ok(1) => self.start(),
+                Ok(_) => self.split(),
+                Err(e) => log::info!("{e}"),
+            }
+        }
+    }
+
+    #[test]
+    fn harness() {
+        let mut splitter = Harness::<Counter>::new();
+        splitter.update();
+        let game = spawn("Game.exe");
+        game.map(0x1000, 0u32.to_le_bytes());
+        splitter.update();
+        assert_eq!(state(), TimerState::NotRunning);
+        game.write(0x1000, &1u32.to_le_bytes());
+        splitter.update();
+        assert_eq!(state(), TimerState::Running);
+        game.write(0x1000, &2u32.to_le_bytes());
+        splitter.update();
+        assert_eq!(split_index(), 1);
+        game.close();
+        splitter.update();
+        assert!(log().last().unwrap().ends_with("the process isn't open"));
+        assert!(splitter.splitter().game.get().is_none());
+
+        assert_eq!(game_time(), None);
+        with_host(|h| h.game_time = Some((-1, 500_000_000)));
+        assert_eq!(game_time(), None);
+        with_host(|h| h.game_time = Some((2, 500_000_000)));
+        assert_eq!(game_time(), Some(Duration::from_millis(2500)));
+    }
+}
@@ -0,0 +1,114 @@
+use core::fmt::{self, Write};
+use core::time::Duration;
+
+use crate::global::Global;
+
+/// Log a message like [`log::log!`], but skip it if this same call logged the
+/// same message within the last `window`. Messages that change are always
+/// logged right away, so this is a good fit for printing some state every
+/// tick while only seeing it when something happens.
+///
+/// ```no_run
+/// # use core::time::Duration;
+/// # use log::Level;
+/// # let (level, x) = (1, 2.0);
+/// livesplit_wrapper::log_throttled!(
+///     Duration::from_secs(1),
+///     Level::Info,
+///     "level {level} at x = {x}"
+/// );
+/// ```
+///
+/// The window is measured by counting ticks at the
+/// [current tick rate](crate::HostFunctions::current_tick_rate), so it runs
+/// long when the runtime falls behind that rate.
+#[macro_export]
+macro_rules! log_throttled {
+    ($window:expr, $level:expr, $($arg:tt)+) => {{
+        static THROTTLE: $crate::__Throttle = $crate::__Throttle::new();
+        match format_args!($($arg)+) {
+            args => {
+                if THROTTLE.allow($window, &args) {
+                    $crate::log::log!($level, "{}", args);
+                }
+            }
+        }
+    }};
+}
+
+/// The state kept for each call site of [`log_throttled!`].
+#[doc(hidden)]
+pub struct __Throttle {
+    last_tick: Global<u64>,
+    last_hash: Global<u64>,
+}
+
+impl __Throttle {
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        Self {
+            // nothing has been logged yet
+            last_tick: Global::new(u64::MAX),
+            last_hash: Global::new(0),
+        }
+    }
+
+    /// Whether a message should be logged, remembering it if so.
+    pub fn allow(&self, window: Duration, args: &fmt::Arguments) -> bool {
+        let mut hash = Fnv::default();
+        // hashing can't fail
+        hash.write_fmt(*args).ok();
+        let tick = crate::current_tick();
+        let last_tick = self.last_tick.get();
+        if last_tick != u64::MAX && hash.0 == self.last_hash.get() {
+            let elapsed = tick.wrapping_sub(last_tick) as f64 / crate::TICK_RATE.get();
+            if elapsed < window.as_secs_f64() {
+                return false;
+            }
+        }
+        self.last_tick.set(tick);
+        self.last_hash.set(hash.0);
+        true
+    }
+}
+
+/// A 64-bit FNV-1a hash of everything written to it, so messages can be
+/// compared without having to keep them around.
+struct Fnv(u64);
+
+impl Default for Fnv {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Write for Fnv {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for &b in s.as_bytes() {
+            self.0 = (self.0 ^ b as u64).wrapping_mul(0x0100_0000_01b3);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Host, HostFunctions};
+
+    #[test]
+    fn repeated_messages_are_throttled() {
+        let throttle = __Throttle::new();
+        let window = Duration::from_millis(250);
+        Host.set_tick_rate(120.0);
+        let mut logged = 0;
+        for _ in 0..120 {
+            crate::__begin_tick();
+            logged += throttle.allow(window, &format_args!("x = {}", 1)) as u32;
+        }
+        assert_eq!(logged, 4);
+        // a different message goes through right away, but only once
+        assert!(throttle.allow(window, &format_args!("x = {}", 2)));
+        assert!(!throttle.allow(window, &format_args!("x = {}", 2)));
+    }
+}
@@ -0,0 +1,126 @@
+//! Turning the ways games store their in-game timer into [`Duration`]s for
+//! [`set_game_time`](crate::HostFunctions::set_game_time).
+//!
+//! Everything here is done with integers, so converting the same value always
+//! gives exactly the same time. That matters for frame counts especially,
+//! since a time that's off by a nanosecond can show up as a different
+//! millisecond in LiveSplit.
+
+use core::time::Duration;
+
+const NANOS_PER_SEC: u128 = 1_000_000_000;
+
+/// How many frames a game runs per second, as a fraction so that rates like
+/// NTSC's 59.94 are exact.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameRate {
+    frames: u64,
+    per_secs: u64,
+}
+
+impl FrameRate {
+    /// 30 frames per second.
+    pub const FPS_30: Self = Self::new(30, 1);
+    /// 50 frames per second, which is what PAL consoles run at.
+    pub const FPS_50: Self = Self::new(50, 1);
+    /// 60 frames per second.
+    pub const FPS_60: Self = Self::new(60, 1);
+    /// NTSC's 29.97 frames per second.
+    pub const NTSC_30: Self = Self::new(30000, 1001);
+    /// NTSC's 59.94 frames per second.
+    pub const NTSC_60: Self = Self::new(60000, 1001);
+
+    /// `frames` frames every `per_secs` seconds.
+    ///
+    /// # Panics
+    ///
+    /// If either is 0.
+    pub const fn new(frames: u64, per_secs: u64) -> Self {
+        assert!(frames > 0 && per_secs > 0, "frame rate must be positive");
+        Self { frames, per_secs }
+    }
+
+    /// How long `count` frames take, rounded down to the nanosecond.
+    pub const fn frames(self, count: u64) -> Duration {
+        let nanos = count as u128 * self.per_secs as u128 * NANOS_PER_SEC / self.frames as u128;
+        nanos_to_duration(nanos)
+    }
+}
+
+/// A time stored as hundredths of a second.
+pub const fn centis(count: u64) -> Duration {
+    Duration::new(count / 100, (count % 100) as u32 * 10_000_000)
+}
+
+/// A time stored as four bytes for hours, minutes, seconds and frames, in
+/// that order.
+pub const fn hmsf(bytes: [u8; 4], rate: FrameRate) -> Duration {
+    let [hours, minutes, secs, frames] = bytes;
+    let secs = hours as u64 * 3600 + minutes as u64 * 60 + secs as u64;
+    Duration::from_secs(secs).saturating_add(rate.frames(frames as u64))
+}
+
+/// Like [`hmsf`], but with each byte in binary-coded decimal (so 59 seconds
+/// is stored as `0x59`), which older consoles often use. Returns `None` if a
+/// byte isn't valid BCD, which usually means the wrong address was read.
+pub const fn hmsf_bcd(bytes: [u8; 4], rate: FrameRate) -> Option<Duration> {
+    let mut decoded = [0; 4];
+    let mut i = 0;
+    while i < 4 {
+        let (high, low) = (bytes[i] >> 4, bytes[i] & 0xF);
+        if high > 9 || low > 9 {
+            return None;
+        }
+        decoded[i] = high * 10 + low;
+        i += 1;
+    }
+    Some(hmsf(decoded, rate))
+}
+
+/// A time stored in seconds as a fixed-point number with `frac_bits` bits
+/// after the point, so `fixed_point(value, 16)` reads a 16.16 number. Rounded
+/// down to the nanosecond.
+///
+/// # Panics
+///
+/// If `frac_bits` is more than 64.
+pub const fn fixed_point(value: u64, frac_bits: u32) -> Duration {
+    assert!(frac_bits <= 64, "at most 64 fractional bits");
+    nanos_to_duration((value as u128 * NANOS_PER_SEC) >> frac_bits)
+}
+
+const fn nanos_to_duration(nanos: u128) -> Duration {
+    let secs = nanos / NANOS_PER_SEC;
+    if secs > u64::MAX as u128 {
+        return Duration::MAX;
+    }
+    Duration::new(secs as u64, (nanos % NANOS_PER_SEC) as u32)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn converts_exactly() {
+        // 1001 / 60000 seconds per frame doesn't round trip through a float
+        assert_eq!(FrameRate::NTSC_60.frames(60000), Duration::from_secs(1001));
+        assert_eq!(
+            FrameRate::NTSC_60.frames(1),
+            Duration::from_nanos(16_683_333)
+        );
+        assert_eq!(FrameRate::FPS_60.frames(90), Duration::from_millis(1500));
+        assert_eq!(centis(12_345), Duration::from_millis(123_450));
+        assert_eq!(
+            hmsf([1, 2, 3, 30], FrameRate::FPS_60),
+            Duration::from_millis(3_723_500)
+        );
+        assert_eq!(
+            hmsf_bcd([0x01, 0x59, 0x59, 0x25], FrameRate::FPS_50),
+            Some(Duration::from_millis(7_199_500))
+        );
+        assert_eq!(hmsf_bcd([0, 0x5A, 0, 0], FrameRate::FPS_50), None);
+        assert_eq!(fixed_point(0x0003_8000, 16), Duration::from_millis(3500));
+        assert_eq!(fixed_point(u64::MAX, 0), Duration::from_secs(u64::MAX));
+    }
+}
@@ -0,0 +1,918 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::time::Duration;
+
+use crate::{HostFunctions, TimerState, Variable};
+
+/// Splits through a fixed list of checkpoints in order, once each. This covers
+/// the common case of a linear route where every split has its own condition.
+///
+/// ```no_run
+/// # use livesplit_wrapper::{Host, SplitSequence, Splitter};
+/// # struct MySplitter { splits: SplitSequence, level: u32 }
+/// # impl Splitter for MySplitter {
+/// # fn new() -> Self { todo!() }
+/// fn update(&mut self) {
+///     let cond = match self.splits.current() {
+///         Some("Forsaken City") => self.level == 2,
+///         Some("Old Site") => self.level == 3,
+///         _ => false,
+///     };
+///     self.splits.advance_if(cond, &Host);
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct SplitSequence {
+    names: &'static [&'static str],
+    index: usize,
+}
+
+impl SplitSequence {
+    /// Create a sequence of checkpoints, starting at the first one.
+    pub const fn new(names: &'static [&'static str]) -> Self {
+        Self { names, index: 0 }
+    }
+
+    /// The name of the next checkpoint to split at, or `None` once they've all
+    /// been reached.
+    pub fn current(&self) -> Option<&'static str> {
+        self.names.get(self.index).copied()
+    }
+
+    /// The index of the next checkpoint to split at.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Split and move on to the next checkpoint if `cond` is true. Whenever the
+    /// timer isn't running the sequence goes back to the first checkpoint, so
+    /// this also handles the run being reset. Returns whether a split happened.
+    pub fn advance_if(&mut self, cond: bool, host: &impl HostFunctions) -> bool {
+        if host.state() == TimerState::NotRunning {
+            self.index = 0;
+            return false;
+        }
+        if !cond || self.current().is_none() {
+            return false;
+        }
+        host.split();
+        self.index += 1;
+        true
+    }
+}
+
+/// Which splits a [`SplitManager`] lets through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplitPolicy {
+    /// Only the next split in the list, so the route has to be followed in
+    /// order.
+    InOrder,
+    /// Any split that hasn't happened yet this run, for games where the
+    /// route can visit things in any order.
+    AnyOrder,
+}
+
+/// Keeps track of which named splits have happened during the current run,
+/// so each one only splits once and, depending on the [`SplitPolicy`], only
+/// in order. Unlike [`SplitSequence`] the conditions don't have to be
+/// checked one at a time, the game's events can just be reported as they
+/// happen:
+///
+/// ```no_run
+/// # use livesplit_wrapper::{Host, SplitManager, SplitPolicy, Splitter};
+/// # struct MySplitter { splits: SplitManager, boss_hp: u32 }
+/// # impl Splitter for MySplitter {
+/// # fn new() -> Self { todo!() }
+/// fn update(&mut self) {
+///     if self.boss_hp == 0 {
+///         self.splits.fire("boss_1", &Host);
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct SplitManager {
+    names: &'static [&'static str],
+    fired: Vec<bool>,
+    policy: SplitPolicy,
+}
+
+impl SplitManager {
+    /// Create a manager for the splits in `names`, in route order.
+    pub fn new(names: &'static [&'static str], policy: SplitPolicy) -> Self {
+        Self {
+            names,
+            fired: vec![false; names.len()],
+            policy,
+        }
+    }
+
+    /// Split for `name` if the policy allows it and it hasn't already split
+    /// this run. Whenever the timer isn't running every split is forgotten,
+    /// like with [`reset`](SplitManager::reset). Returns whether a split
+    /// happened, which is never the case for names that aren't in the list.
+    pub fn fire(&mut self, name: &str, host: &impl HostFunctions) -> bool {
+        if host.state() == TimerState::NotRunning {
+            self.reset();
+            return false;
+        }
+        let Some(i) = self.names.iter().position(|&n| n == name) else {
+            return false;
+        };
+        let allowed = match self.policy {
+            SplitPolicy::InOrder => self.fired.iter().position(|&f| !f) == Some(i),
+            SplitPolicy::AnyOrder => !self.fired[i],
+        };
+        if allowed {
+            host.split();
+            self.fired[i] = true;
+        }
+        allowed
+    }
+
+    /// Whether `name` has split during this run.
+    pub fn has_fired(&self, name: &str) -> bool {
+        self.names
+            .iter()
+            .zip(&self.fired)
+            .any(|(&n, &fired)| n == name && fired)
+    }
+
+    /// How many splits have happened during this run.
+    pub fn fired_count(&self) -> usize {
+        self.fired.iter().filter(|&&f| f).count()
+    }
+
+    /// Forget which splits have happened. This happens on its own when
+    /// [`fire`](SplitManager::fire) sees that no run is going, but a run
+    /// that's reset and started again in between calls needs this, for
+    /// example from [`on_state_change`](crate::Splitter::on_state_change).
+    pub fn reset(&mut self) {
+        self.fired.fill(false);
+    }
+}
+
+/// Starts the timer when the player goes into gameplay, for games where that's
+/// the start condition. Only the change from not being in gameplay to being
+/// in it counts, so a game that's already in gameplay when the splitter
+/// starts (or when the runner resets) doesn't start a run right away.
+///
+/// This is a struct rather than a [`HostFunctions`] method because the edge
+/// can only be found by remembering the previous value, and that has to be
+/// kept per start condition rather than once for the whole splitter.
+#[derive(Debug, Default, Clone)]
+pub struct AutoStart {
+    was_in_game: bool,
+}
+
+impl AutoStart {
+    /// Create one that starts out not in gameplay.
+    pub const fn new() -> Self {
+        Self { was_in_game: false }
+    }
+
+    /// Call this every tick with whether the player is in gameplay, and the
+    /// timer will be started when that goes from `false` to `true`. Nothing
+    /// happens if a run is already going, so a run that was started by hand
+    /// isn't affected. Returns whether the timer was started.
+    pub fn update(&mut self, in_game: bool, host: &impl HostFunctions) -> bool {
+        let was_in_game = core::mem::replace(&mut self.was_in_game, in_game);
+        let start = in_game && !was_in_game && host.state() == TimerState::NotRunning;
+        if start {
+            host.start();
+        }
+        start
+    }
+}
+
+/// Suppresses splits that happen too soon after the previous one, so a value
+/// that flickers across a threshold for a few ticks only causes one split.
+#[derive(Debug, Clone)]
+pub struct Debounced {
+    min_ticks: u64,
+    last_split: Option<u64>,
+}
+
+impl Debounced {
+    /// Only allow a split if there hasn't been one in the last `min_ticks`
+    /// updates.
+    pub const fn new(min_ticks: u32) -> Self {
+        Self {
+            min_ticks: min_ticks as u64,
+            last_split: None,
+        }
+    }
+
+    /// Split unless this already split within the last `min_ticks` updates.
+    /// Resetting the run clears the cooldown. Returns whether a split
+    /// happened.
+    pub fn split(&mut self, host: &impl HostFunctions) -> bool {
+        if host.state() == TimerState::NotRunning {
+            self.last_split = None;
+            return false;
+        }
+        let tick = crate::current_tick();
+        if self
+            .last_split
+            .is_some_and(|last| tick - last < self.min_ticks)
+        {
+            return false;
+        }
+        host.split();
+        self.last_split = Some(tick);
+        true
+    }
+}
+
+/// Only resets the run once the reset condition has held for several updates
+/// in a row, so a value that's wrong for a single frame (like while the game
+/// is loading) can't throw away a run.
+#[derive(Debug, Clone)]
+pub struct ResetGuard {
+    ticks: u32,
+    held: u32,
+}
+
+impl ResetGuard {
+    /// Require the condition to hold for `ticks` consecutive updates.
+    pub const fn new(ticks: u32) -> Self {
+        Self { ticks, held: 0 }
+    }
+
+    /// Call this every tick with whether the run should be reset. Once `cond`
+    /// has been true for enough updates in a row the run is reset, and any
+    /// update where it's false starts the count over. Returns whether a reset
+    /// happened.
+    pub fn reset_if(&mut self, cond: bool, host: &impl HostFunctions) -> bool {
+        if !cond || host.state() == TimerState::NotRunning {
+            self.held = 0;
+            return false;
+        }
+        self.held += 1;
+        if self.held < self.ticks {
+            return false;
+        }
+        host.reset();
+        self.held = 0;
+        true
+    }
+}
+
+/// Starts and splits only when a condition becomes true, rather than on every
+/// tick that it's true. Calling [`start`](HostFunctions::start) or
+/// [`split`](HostFunctions::split) directly while a condition holds splits
+/// over and over, so this remembers each condition from the last tick and
+/// only acts when it goes from `false` to `true`.
+///
+/// ```no_run
+/// # use livesplit_wrapper::{Host, Splitter, TimerControl};
+/// # struct MySplitter { timer: TimerControl, in_menu: bool, level: u32 }
+/// # impl Splitter for MySplitter {
+/// # fn new() -> Self { todo!() }
+/// fn update(&mut self) {
+///     self.timer.start_if(!self.in_menu, &Host);
+///     self.timer.split_if(self.level == 0, &Host);
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TimerControl {
+    start_cond: bool,
+    split_cond: bool,
+    last_action: Option<u64>,
+}
+
+impl TimerControl {
+    /// Create a helper that hasn't seen either condition be true yet.
+    pub const fn new() -> Self {
+        Self {
+            start_cond: false,
+            split_cond: false,
+            last_action: None,
+        }
+    }
+
+    /// Call this every tick with the start condition. The timer is started
+    /// when `cond` becomes true while no run is going, so a run that's
+    /// already going (or one that was just reset while `cond` still holds)
+    /// isn't started again. Returns whether the timer was started.
+    pub fn start_if(&mut self, cond: bool, host: &impl HostFunctions) -> bool {
+        let rising = cond && !self.start_cond;
+        self.start_cond = cond;
+        if !rising || host.state() != TimerState::NotRunning {
+            return false;
+        }
+        host.start();
+        self.last_action = Some(crate::current_tick());
+        true
+    }
+
+    /// Call this every tick with the split condition. The run is split when
+    /// `cond` becomes true while it's going, at most once per tick and never
+    /// on the tick it was started. Returns whether a split happened.
+    pub fn split_if(&mut self, cond: bool, host: &impl HostFunctions) -> bool {
+        let rising = cond && !self.split_cond;
+        self.split_cond = cond;
+        let tick = crate::current_tick();
+        if !rising || !host.state().is_running_or_paused() || self.last_action == Some(tick) {
+            return false;
+        }
+        host.split();
+        self.last_action = Some(tick);
+        true
+    }
+}
+
+/// A change in the timer's state, as recorded by [`StateHistory`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Transition {
+    /// The state before the change.
+    pub from: TimerState,
+    /// The state after the change.
+    pub to: TimerState,
+    /// The tick that the change was noticed on.
+    pub tick: u64,
+}
+
+/// Remembers the last `N` changes to the timer's state, which can help figure
+/// out why a split fired (or didn't). Return it from
+/// [`Splitter::history`](crate::Splitter::history) and the runner records
+/// every change before calling
+/// [`on_state_change`](crate::Splitter::on_state_change):
+///
+/// ```no_run
+/// # use livesplit_wrapper::{RecordStates, StateHistory, Splitter, TimerState};
+/// # struct MySplitter { history: StateHistory<8> }
+/// # impl Splitter for MySplitter {
+/// # fn new() -> Self { todo!() }
+/// # fn update(&mut self) {}
+/// fn history(&mut self) -> Option<&mut dyn RecordStates> {
+///     Some(&mut self.history)
+/// }
+///
+/// fn on_state_change(&mut self, _: TimerState, _: TimerState) {
+///     log::info!(
+///         "recent states: {:?}",
+///         self.history.iter().collect::<Vec<_>>()
+///     );
+/// }
+/// # }
+/// ```
+///
+/// You can also [`push`](StateHistory::push) to it yourself.
+#[derive(Debug, Clone)]
+pub struct StateHistory<const N: usize> {
+    transitions: [Option<Transition>; N],
+    next: usize,
+}
+
+impl<const N: usize> StateHistory<N> {
+    /// Create an empty history.
+    pub const fn new() -> Self {
+        Self {
+            transitions: [None; N],
+            next: 0,
+        }
+    }
+
+    /// Record a change, forgetting the oldest one if the history is full.
+    pub fn push(&mut self, from: TimerState, to: TimerState) {
+        if N == 0 {
+            return;
+        }
+        let tick = crate::current_tick();
+        self.transitions[self.next] = Some(Transition { from, to, tick });
+        self.next = (self.next + 1) % N;
+    }
+
+    /// The recorded changes, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = Transition> + '_ {
+        let (newer, older) = self.transitions.split_at(self.next);
+        older.iter().chain(newer).flatten().copied()
+    }
+
+    /// The most recent change.
+    pub fn last(&self) -> Option<Transition> {
+        self.transitions[(self.next + N.checked_sub(1)?) % N]
+    }
+}
+
+/// Something the runner can record timer state changes into, see
+/// [`Splitter::history`](crate::Splitter::history).
+pub trait RecordStates {
+    /// Record a change from `from` to `to`.
+    fn record(&mut self, from: TimerState, to: TimerState);
+}
+
+impl<const N: usize> RecordStates for StateHistory<N> {
+    fn record(&mut self, from: TimerState, to: TimerState) {
+        self.push(from, to);
+    }
+}
+
+impl<const N: usize> Default for StateHistory<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Pauses game time while the game is loading, only calling
+/// [`pause`](HostFunctions::pause) and [`unpause`](HostFunctions::unpause)
+/// when the loading state actually changes.
+#[derive(Debug, Default, Clone)]
+pub struct LoadingTimer {
+    loading: bool,
+    loading_time: Duration,
+}
+
+impl LoadingTimer {
+    /// Create a timer that starts out not loading.
+    pub const fn new() -> Self {
+        Self {
+            loading: false,
+            loading_time: Duration::ZERO,
+        }
+    }
+
+    /// Call this every tick with whether the game is currently loading. When
+    /// there's no run going this does nothing except forget the previous
+    /// state, so the next run gets paused properly if it starts in a load.
+    pub fn set_loading(&mut self, loading: bool, host: &impl HostFunctions) {
+        if !host.state().is_running_or_paused() {
+            *self = Self::new();
+            return;
+        }
+        match (self.loading, loading) {
+            (false, true) => host.pause(),
+            (true, false) => host.unpause(),
+            (true, true) => {
+                self.loading_time += Duration::from_secs_f64(1.0 / host.current_tick_rate())
+            }
+            (false, false) => {}
+        }
+        self.loading = loading;
+    }
+
+    /// Whether the game was loading as of the last tick.
+    pub fn is_loading(&self) -> bool {
+        self.loading
+    }
+
+    /// Roughly how long has been spent loading this run. There's no clock to
+    /// measure this with, so it's estimated from the number of ticks spent
+    /// loading and the [current tick rate](HostFunctions::current_tick_rate).
+    pub fn loading_time(&self) -> Duration {
+        self.loading_time
+    }
+}
+
+/// Removes loads from game time like [`LoadingTimer`], but ignores loads
+/// shorter than a minimum, for games where the loading flag flickers on for a
+/// moment at times that aren't really loads. Game time is only paused once a
+/// load has gone on for that long, so the start of every load still counts,
+/// and the minimum should be kept short next to the game's real loads. Game
+/// time is never set, so this works alongside anything else that sets it and
+/// can be created partway through a run.
+///
+/// How much time was removed this run can be shown as a variable, which is
+/// only sent when it changes.
+///
+/// ```no_run
+/// # use core::time::Duration;
+/// # use livesplit_wrapper::{Host, LoadRemover};
+/// # let loading = false;
+/// let mut loads = LoadRemover::new(Duration::from_millis(100)).with_variable("Load time");
+/// // every tick
+/// loads.update(loading, &Host);
+/// ```
+#[derive(Debug, Clone)]
+pub struct LoadRemover {
+    min_load: Duration,
+    variable: Option<Variable>,
+    loading_for: Option<Duration>,
+    paused: bool,
+    removed: Duration,
+}
+
+impl LoadRemover {
+    /// Ignore loads shorter than `min_load`. Use [`Duration::ZERO`] to pause
+    /// as soon as a load starts.
+    pub const fn new(min_load: Duration) -> Self {
+        Self {
+            min_load,
+            variable: None,
+            loading_for: None,
+            paused: false,
+            removed: Duration::ZERO,
+        }
+    }
+
+    /// Show the time removed this run in the variable called `key`.
+    pub fn with_variable(mut self, key: &'static str) -> Self {
+        self.variable = Some(Variable::new(key));
+        self
+    }
+
+    /// Call this every tick with whether the game is currently loading. Time
+    /// is measured with [`tick_delta`](HostFunctions::tick_delta). While
+    /// there's no run going this forgets the current load and starts the
+    /// removed time back at zero.
+    pub fn update(&mut self, loading: bool, host: &impl HostFunctions) {
+        let state = host.state();
+        if !state.is_running_or_paused() {
+            self.loading_for = None;
+            self.paused = false;
+            self.removed = Duration::ZERO;
+        } else if loading {
+            let delta = host.tick_delta();
+            let loading_for = match self.loading_for {
+                // nothing's known about the tick the load started on
+                None => Duration::ZERO,
+                Some(time) => time + delta,
+            };
+            self.loading_for = Some(loading_for);
+            if self.paused {
+                // while the runner has the timer paused nothing is removed
+                if state == TimerState::Running {
+                    self.removed += delta;
+                }
+            } else if loading_for >= self.min_load {
+                host.pause();
+                self.paused = true;
+            }
+        } else {
+            self.loading_for = None;
+            if self.paused {
+                host.unpause();
+                self.paused = false;
+            }
+        }
+        if let Some(variable) = &mut self.variable {
+            variable.set_duration(self.removed, host);
+        }
+    }
+
+    /// Whether game time is paused for a load right now.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// How long game time has been paused for loads this run, going by
+    /// [`tick_delta`](HostFunctions::tick_delta). Time the runner had the
+    /// timer paused doesn't count, since it wasn't running anyway.
+    pub fn removed(&self) -> Duration {
+        self.removed
+    }
+}
+
+/// Keeps track of the total in-game time for games that don't have a single
+/// timer covering the whole run, and sends it to the host with
+/// [`set_game_time`](HostFunctions::set_game_time) whenever it changes.
+///
+/// The first update of each run [pauses](HostFunctions::pause) game time, so
+/// LiveSplit doesn't keep counting in between updates and the time shown is
+/// exactly the total from here. Don't also use a [`LoadingTimer`], since it
+/// unpauses game time again after loads.
+#[derive(Debug, Default, Clone)]
+pub struct IgtTimer {
+    total: Duration,
+    last_counter: Option<Duration>,
+    sent: Option<Duration>,
+}
+
+impl IgtTimer {
+    /// Create a timer starting at zero.
+    pub const fn new() -> Self {
+        Self {
+            total: Duration::ZERO,
+            last_counter: None,
+            sent: None,
+        }
+    }
+
+    /// Add the time that passed since the last tick, unless the game was
+    /// `paused` during it. While the timer isn't running this resets the
+    /// total instead, so every run starts back at zero.
+    pub fn add_delta(&mut self, delta: Duration, paused: bool, host: &impl HostFunctions) {
+        if host.state() == TimerState::NotRunning {
+            *self = Self::new();
+            return;
+        }
+        if self.sent.is_none() {
+            host.pause();
+        }
+        if !paused {
+            self.total += delta;
+        }
+        if self.sent != Some(self.total) {
+            host.set_game_time(self.total);
+            self.sent = Some(self.total);
+        }
+    }
+
+    /// Accumulate time from a counter in the game that only goes up while
+    /// playing, like a per-level timer. When the counter goes backwards it's
+    /// assumed to have restarted from zero, so nothing is lost between levels.
+    /// Like [`add_delta`](IgtTimer::add_delta) this resets while the timer
+    /// isn't running.
+    pub fn update_counter(&mut self, counter: Duration, host: &impl HostFunctions) {
+        let delta = match self.last_counter {
+            Some(last) => counter.checked_sub(last).unwrap_or(counter),
+            // the first value is just the starting point
+            None => Duration::ZERO,
+        };
+        self.add_delta(delta, false, host);
+        if host.state() != TimerState::NotRunning {
+            self.last_counter = Some(counter);
+        }
+    }
+
+    /// The accumulated game time for the current run.
+    pub fn total(&self) -> Duration {
+        self.total
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{mock, Host};
+    use alloc::vec::Vec;
+
+    #[test]
+    fn split_sequence() {
+        let mut splits = SplitSequence::new(&["a", "b"]);
+        assert!(!splits.advance_if(true, &Host));
+        Host.start();
+        assert!(!splits.advance_if(false, &Host));
+        assert!(splits.advance_if(true, &Host));
+        assert_eq!(splits.current(), Some("b"));
+        assert!(splits.advance_if(true, &Host));
+        assert_eq!(splits.current(), None);
+        assert!(!splits.advance_if(true, &Host));
+        mock::with_host(|h| assert_eq!(h.splits, 2));
+
+        Host.reset();
+        splits.advance_if(false, &Host);
+        assert_eq!(splits.index(), 0);
+    }
+
+    #[test]
+    fn timer_control_latches() {
+        let mut timer = TimerControl::new();
+        crate::__begin_tick();
+        assert!(timer.start_if(true, &Host));
+        assert!(!timer.split_if(true, &Host));
+        crate::__begin_tick();
+        assert!(!timer.start_if(true, &Host));
+        assert!(!timer.split_if(true, &Host));
+        timer.split_if(false, &Host);
+        crate::__begin_tick();
+        assert!(timer.split_if(true, &Host));
+        mock::with_host(|h| assert_eq!(h.splits, 1));
+
+        // still holding the start condition after a reset doesn't restart
+        Host.reset();
+        crate::__begin_tick();
+        assert!(!timer.start_if(true, &Host));
+        timer.start_if(false, &Host);
+        assert!(timer.start_if(true, &Host));
+    }
+
+    #[test]
+    fn split_manager_policies() {
+        const NAMES: &[&str] = &["boss_1", "boss_2", "boss_3"];
+        let mut in_order = SplitManager::new(NAMES, SplitPolicy::InOrder);
+        let mut any_order = SplitManager::new(NAMES, SplitPolicy::AnyOrder);
+        assert!(!in_order.fire("boss_1", &Host));
+        Host.start();
+        assert!(!in_order.fire("boss_2", &Host));
+        assert!(in_order.fire("boss_1", &Host));
+        assert!(!in_order.fire("boss_1", &Host));
+        assert!(in_order.fire("boss_2", &Host));
+        assert!(!in_order.fire("secret", &Host));
+
+        assert!(any_order.fire("boss_3", &Host));
+        assert!(!any_order.fire("boss_3", &Host));
+        assert!(any_order.fire("boss_1", &Host));
+        assert!(any_order.has_fired("boss_1"));
+        assert!(!any_order.has_fired("boss_2"));
+        assert_eq!(any_order.fired_count(), 2);
+        mock::with_host(|h| assert_eq!(h.splits, 4));
+
+        Host.reset();
+        assert!(!any_order.fire("boss_2", &Host));
+        assert_eq!(any_order.fired_count(), 0);
+    }
+
+    #[test]
+    fn auto_start_on_rising_edge() {
+        let mut auto_start = AutoStart::new();
+        assert!(!auto_start.update(false, &Host));
+        assert!(auto_start.update(true, &Host));
+        assert!(!auto_start.update(true, &Host));
+        assert_eq!(Host.state(), TimerState::Running);
+        // already running, so the next edge is ignored
+        assert!(!auto_start.update(false, &Host));
+        assert!(!auto_start.update(true, &Host));
+        // starting in gameplay after a reset needs another edge
+        Host.reset();
+        assert!(!auto_start.update(true, &Host));
+        assert!(!auto_start.update(false, &Host));
+        assert!(auto_start.update(true, &Host));
+        mock::with_host(|h| assert_eq!(h.resets, 1));
+
+        // each one keeps its own edge
+        let mut other = AutoStart::new();
+        Host.reset();
+        assert!(other.update(true, &Host));
+    }
+
+    #[test]
+    fn debounced_split() {
+        let mut debounced = Debounced::new(3);
+        Host.start();
+        crate::__begin_tick();
+        assert!(debounced.split(&Host));
+        crate::__begin_tick();
+        assert!(!debounced.split(&Host));
+        crate::__begin_tick();
+        crate::__begin_tick();
+        assert!(debounced.split(&Host));
+        mock::with_host(|h| assert_eq!(h.splits, 2));
+
+        Host.reset();
+        assert!(!debounced.split(&Host));
+        Host.start();
+        assert!(debounced.split(&Host));
+    }
+
+    #[test]
+    fn reset_guard_ignores_glitches() {
+        let mut guard = ResetGuard::new(3);
+        Host.start();
+        for cond in [true, false, true, true, false] {
+            assert!(!guard.reset_if(cond, &Host));
+        }
+        mock::with_host(|h| assert_eq!(h.resets, 0));
+        assert!(!guard.reset_if(true, &Host));
+        assert!(!guard.reset_if(true, &Host));
+        assert!(guard.reset_if(true, &Host));
+        assert!(!guard.reset_if(true, &Host));
+        mock::with_host(|h| assert_eq!(h.resets, 1));
+    }
+
+    #[test]
+    fn bounded_state_history() {
+        use TimerState::*;
+        let mut history = StateHistory::<3>::new();
+        assert_eq!(history.last(), None);
+        let states = [NotRunning, Running, Paused, Running, Ended, NotRunning];
+        for pair in states.windows(2) {
+            crate::__begin_tick();
+            history.push(pair[0], pair[1]);
+        }
+        let recent: Vec<_> = history.iter().map(|t| (t.from, t.to)).collect();
+        assert_eq!(
+            recent,
+            [(Paused, Running), (Running, Ended), (Ended, NotRunning)]
+        );
+        let last = history.last().unwrap();
+        assert_eq!((last.to, last.tick), (NotRunning, 5));
+    }
+
+    #[derive(Default)]
+    struct Recorder {
+        history: StateHistory<4>,
+    }
+
+    impl crate::Splitter for Recorder {
+        fn new() -> Self {
+            Self::default()
+        }
+
+        fn history(&mut self) -> Option<&mut dyn RecordStates> {
+            Some(&mut self.history)
+        }
+
+        fn update(&mut self) {}
+    }
+
+    #[test]
+    fn history_recorded_by_the_runner() {
+        use TimerState::*;
+        let mut splitter = crate::testing::Harness::<Recorder>::new();
+        splitter.update();
+        Host.start();
+        splitter.update();
+        splitter.update();
+        Host.reset();
+        splitter.update();
+        let recorded: Vec<_> = splitter
+            .splitter()
+            .history
+            .iter()
+            .map(|t| (t.from, t.to))
+            .collect();
+        assert_eq!(recorded, [(NotRunning, Running), (Running, NotRunning)]);
+    }
+
+    #[test]
+    fn loading_timer_edges() {
+        let mut timer = LoadingTimer::new();
+        Host.start();
+        Host.set_tick_rate(10.0);
+        for loading in [false, true, true, true, false, false, true] {
+            timer.set_loading(loading, &Host);
+        }
+        assert!(timer.is_loading());
+        assert_eq!(timer.loading_time(), Duration::from_millis(200));
+        mock::with_host(|h| {
+            assert_eq!(h.pauses, 2);
+            assert_eq!(h.resumes, 1);
+        });
+
+        Host.reset();
+        timer.set_loading(true, &Host);
+        assert!(!timer.is_loading());
+        assert_eq!(timer.loading_time(), Duration::ZERO);
+    }
+
+    #[test]
+    fn load_remover_ignores_short_loads() {
+        let mut loads = LoadRemover::new(Duration::from_millis(150)).with_variable("loads");
+        crate::TICK_DELTA.set(0.1);
+        Host.start();
+        // a 2 tick flicker, then a 5 tick load
+        for loading in [
+            false, true, true, false, true, true, true, true, true, false,
+        ] {
+            loads.update(loading, &Host);
+        }
+        assert!(!loads.is_paused());
+        assert_eq!(loads.removed(), Duration::from_millis(200));
+        mock::with_host(|h| {
+            assert_eq!((h.pauses, h.resumes), (1, 1));
+            assert_eq!(h.game_time, None);
+            let last = h.variables.last().unwrap();
+            assert_eq!((last.0.as_str(), last.1.as_str()), ("loads", "00:00.200"));
+        });
+
+        Host.reset();
+        loads.update(false, &Host);
+        assert_eq!(loads.removed(), Duration::ZERO);
+        mock::with_host(|h| assert_eq!(h.variables.last().unwrap().1, "00:00.000"));
+    }
+
+    #[test]
+    fn load_remover_after_manual_pause() {
+        let mut loads = LoadRemover::new(Duration::from_millis(100));
+        crate::TICK_DELTA.set(0.1);
+        Host.start();
+        loads.update(false, &Host);
+        // the runner pauses, then a long load starts
+        mock::with_host(|h| h.state = TimerState::Paused as u32);
+        for _ in 0..4 {
+            loads.update(true, &Host);
+        }
+        assert!(loads.is_paused());
+        assert_eq!(loads.removed(), Duration::ZERO);
+        mock::with_host(|h| h.state = TimerState::Running as u32);
+        for loading in [true, true, false] {
+            loads.update(loading, &Host);
+        }
+        assert_eq!(loads.removed(), Duration::from_millis(200));
+        mock::with_host(|h| {
+            assert_eq!((h.pauses, h.resumes), (1, 1));
+            assert_eq!(h.game_time, None);
+        });
+    }
+
+    #[test]
+    fn igt_timer_skips_pauses() {
+        let mut igt = IgtTimer::new();
+        Host.start();
+        let ms = Duration::from_millis;
+        for (delta, paused) in [(10, false), (10, true), (25, true), (5, false)] {
+            igt.add_delta(ms(delta), paused, &Host);
+        }
+        assert_eq!(igt.total(), ms(15));
+        mock::with_host(|h| {
+            assert_eq!(h.game_time, Some((0, 15_000_000)));
+            assert_eq!(h.game_time_sets, 2);
+            assert_eq!(h.pauses, 1);
+        });
+
+        Host.reset();
+        igt.add_delta(ms(10), false, &Host);
+        assert_eq!(igt.total(), Duration::ZERO);
+
+        Host.start();
+        // the level timer restarts partway through
+        for counter in [100, 130, 160, 20, 50] {
+            igt.update_counter(ms(counter), &Host);
+        }
+        assert_eq!(igt.total(), ms(110));
+    }
+}
@@ -0,0 +1,734 @@
+//! Reading the state of Unity games through the Mono runtime's metadata.
+//!
+//! Fields of C# classes don't have fixed addresses, and their offsets change
+//! every time the game is rebuilt, so pointer paths to them break with every
+//! update. Mono keeps the name and offset of every field around though, so
+//! with [`Mono`] a static field can be found by the name of its class and the
+//! name of the field instead:
+//!
+//! ```no_run
+//! # use livesplit_wrapper::{unity::Mono, Process};
+//! # fn f(process: &Process) -> Option<()> {
+//! let mono = Mono::attach(process)?;
+//! let game_manager = mono.get_class(process, "GameManager")?;
+//! let deaths: i32 = game_manager.static_field(process, "deathCount").ok()?;
+//! # Some(())
+//! # }
+//! ```
+//!
+//! Classes are only filled in by the runtime once the game has used them, so
+//! a class that can't be found yet might show up a few ticks later. Looking
+//! things up by name reads a lot of memory, so do it once and keep the
+//! [`Class`] around.
+//!
+//! Games built with IL2CPP (the ones that ship a `GameAssembly.dll` instead
+//! of a Mono module) compile the C# code ahead of time, but keep the same
+//! kind of metadata around, so [`Il2Cpp`] finds classes in them the same way.
+//! Only 64-bit games are supported, and for IL2CPP only the layout used by
+//! Unity 2020 and 2021.
+
+use alloc::string::String;
+
+use crate::{Address, Error, Pod, PointerWidth, Process, Result};
+
+/// The longest name of an assembly, class or field that'll be read.
+const MAX_NAME_LEN: usize = 256;
+
+/// The version of the Mono runtime, which decides where everything is in its
+/// structs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MonoVersion {
+    /// The old Mono in `mono.dll`, used by Unity before 2017.
+    V1,
+    /// The Mono in `mono-2.0-bdwgc.dll`, used by Unity 2017 up to 2021.1.
+    V2,
+    /// The Mono in `mono-2.0-bdwgc.dll` since Unity 2021.2.
+    V3,
+}
+
+/// Offsets into the class and field structs on 64-bit Windows, which Mono
+/// and IL2CPP both have but lay out differently.
+#[derive(Debug)]
+struct ClassOffsets {
+    name: u64,
+    name_space: u64,
+    parent: u64,
+    fields: u64,
+    field_count: u64,
+    field_type: u64,
+    field_name: u64,
+    field_offset: u64,
+    field_size: u64,
+}
+
+/// Offsets into Mono's structs on 64-bit Windows.
+#[derive(Debug)]
+struct Offsets {
+    assembly_name: u64,
+    assembly_image: u64,
+    image_class_cache: u64,
+    hash_table_size: u64,
+    hash_table_table: u64,
+    class: ClassOffsets,
+    class_next_class_cache: u64,
+    class_runtime_info: u64,
+    class_vtable_size: u64,
+    runtime_info_domain_vtables: u64,
+    vtable_vtable: u64,
+}
+
+impl MonoVersion {
+    fn offsets(self) -> &'static Offsets {
+        const V1: Offsets = Offsets {
+            assembly_name: 0x10,
+            assembly_image: 0x58,
+            image_class_cache: 0x3D0,
+            hash_table_size: 0x18,
+            hash_table_table: 0x20,
+            class: ClassOffsets {
+                name: 0x48,
+                name_space: 0x50,
+                parent: 0x30,
+                fields: 0xA8,
+                field_count: 0x94,
+                field_type: 0x0,
+                field_name: 0x8,
+                field_offset: 0x18,
+                field_size: 0x20,
+            },
+            class_next_class_cache: 0x100,
+            class_runtime_info: 0xF8,
+            class_vtable_size: 0x18,
+            runtime_info_domain_vtables: 0x8,
+            vtable_vtable: 0x48,
+        };
+        const V2: Offsets = Offsets {
+            assembly_image: 0x60,
+            image_class_cache: 0x4C0,
+            class: ClassOffsets {
+                fields: 0x98,
+                field_count: 0x100,
+                ..V1.class
+            },
+            class_next_class_cache: 0x108,
+            class_runtime_info: 0xD0,
+            class_vtable_size: 0x5C,
+            vtable_vtable: 0x40,
+            ..V1
+        };
+        const V3: Offsets = Offsets {
+            image_class_cache: 0x4D0,
+            vtable_vtable: 0x48,
+            ..V2
+        };
+        match self {
+            MonoVersion::V1 => &V1,
+            MonoVersion::V2 => &V2,
+            MonoVersion::V3 => &V3,
+        }
+    }
+}
+
+/// Offsets into IL2CPP's structs on 64-bit Windows, for Unity 2020 and 2021.
+const IL2CPP_CLASS: ClassOffsets = ClassOffsets {
+    name: 0x10,
+    name_space: 0x18,
+    parent: 0x58,
+    fields: 0x80,
+    field_count: 0x120,
+    field_type: 0x8,
+    field_name: 0x0,
+    field_offset: 0x18,
+    field_size: 0x20,
+};
+const IL2CPP_ASSEMBLY_NAME: u64 = 0x18;
+const IL2CPP_IMAGE_TYPE_COUNT: u64 = 0x18;
+const IL2CPP_IMAGE_METADATA_HANDLE: u64 = 0x28;
+const IL2CPP_CLASS_STATIC_FIELDS: u64 = 0xB8;
+
+/// Which runtime a class or image belongs to, which decides where everything
+/// is in its structs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Runtime {
+    Mono(MonoVersion),
+    Il2Cpp { type_info_table: Address },
+}
+
+impl Runtime {
+    fn class_offsets(self) -> &'static ClassOffsets {
+        match self {
+            Runtime::Mono(version) => &version.offsets().class,
+            Runtime::Il2Cpp { .. } => &IL2CPP_CLASS,
+        }
+    }
+}
+
+/// The Mono runtime inside a Unity game.
+#[derive(Debug, Clone, Copy)]
+pub struct Mono {
+    assemblies: Address,
+    version: MonoVersion,
+}
+
+impl Mono {
+    /// Find Mono in the process, guessing the version from the name of its
+    /// module. Games on Unity 2021.2 or newer need
+    /// [`attach_version`](Mono::attach_version) with [`MonoVersion::V3`]
+    /// since they use a module with the same name as older ones. Returns
+    /// `None` if Mono isn't loaded yet or the process is 32-bit.
+    pub fn attach(process: &Process) -> Option<Self> {
+        let version = match process.first_module(&["mono-2.0-bdwgc.dll", "mono.dll"])?.0 {
+            "mono.dll" => MonoVersion::V1,
+            _ => MonoVersion::V2,
+        };
+        Self::attach_version(process, version)
+    }
+
+    /// Find a specific version of Mono in the process.
+    pub fn attach_version(process: &Process, version: MonoVersion) -> Option<Self> {
+        if process.pointer_width() != PointerWidth::Bits64 {
+            return None;
+        }
+        let module = match version {
+            MonoVersion::V1 => "mono.dll",
+            MonoVersion::V2 | MonoVersion::V3 => "mono-2.0-bdwgc.dll",
+        };
+        let base = process.module(module)?;
+        let foreach = process.pe_export(base, "mono_assembly_foreach")?;
+        // the function starts by loading the list of assemblies into rcx
+        let mut code = [0; 0x100];
+        let len = process.read_partial(foreach, &mut code).ok()?;
+        let mov = code[..len]
+            .windows(3)
+            .position(|w| w == [0x48, 0x8B, 0x0D])?;
+        Some(Self {
+            assemblies: read_relative(process, foreach + mov as u64 + 3)?,
+            version,
+        })
+    }
+
+    /// The version of Mono this was attached with.
+    pub fn version(&self) -> MonoVersion {
+        self.version
+    }
+
+    /// Find a loaded assembly's image by its name, like `"Assembly-CSharp"`.
+    pub fn image(&self, process: &Process, name: &str) -> Option<Image> {
+        let offsets = self.version.offsets();
+        let mut node = process
+            .read_pointer(self.assemblies, PointerWidth::Bits64)
+            .ok()?;
+        while node != 0 {
+            let assembly = process.read_pointer(node, PointerWidth::Bits64).ok()?;
+            if read_name(process, assembly + offsets.assembly_name).as_deref() == Ok(name) {
+                let addr = process
+                    .read_pointer(assembly + offsets.assembly_image, PointerWidth::Bits64)
+                    .ok()?;
+                return Some(Image {
+                    addr,
+                    runtime: Runtime::Mono(self.version),
+                });
+            }
+            node = process.read_pointer(node + 8, PointerWidth::Bits64).ok()?;
+        }
+        None
+    }
+
+    /// Find a class in `Assembly-CSharp`, which is where the game's own code
+    /// is.
+    pub fn get_class(&self, process: &Process, name: &str) -> Option<Class> {
+        self.image(process, "Assembly-CSharp")?
+            .get_class(process, name)
+    }
+}
+
+/// IL2CPP inside a Unity game, which is what games that ship a
+/// `GameAssembly.dll` use instead of Mono.
+#[derive(Debug, Clone, Copy)]
+pub struct Il2Cpp {
+    assemblies: Address,
+    type_info_table: Address,
+}
+
+impl Il2Cpp {
+    /// Find IL2CPP's lists of assemblies and classes in `GameAssembly.dll`.
+    /// This scans the module for the code that uses them, so only do it once
+    /// and keep the result. Returns `None` if the module isn't loaded yet, the
+    /// code isn't found, or the process is 32-bit.
+    pub fn attach(process: &Process) -> Option<Self> {
+        const ASSEMBLIES: &str = "48 FF C5 80 3C ?? 00 75 ?? 48 8B 1D";
+        const TYPE_INFO_TABLE: &str = "48 83 3C ?? 00 75 ?? 8B ?? E8";
+        if process.pointer_width() != PointerWidth::Bits64 {
+            return None;
+        }
+        let assemblies = process.scan_signature("GameAssembly.dll", ASSEMBLIES)?;
+        let type_info_table = process.scan_signature("GameAssembly.dll", TYPE_INFO_TABLE)?;
+        Some(Self {
+            assemblies: read_relative(process, assemblies + 12)?,
+            type_info_table: read_relative(process, type_info_table - 4)?,
+        })
+    }
+
+    /// Find a loaded assembly's image by its name, like `"Assembly-CSharp"`.
+    pub fn image(&self, process: &Process, name: &str) -> Option<Image> {
+        // the assemblies are a `std::vector` of pointers
+        let start = process
+            .read_pointer(self.assemblies, PointerWidth::Bits64)
+            .ok()?;
+        let end = process
+            .read_pointer(self.assemblies + 8, PointerWidth::Bits64)
+            .ok()?;
+        (start..end).step_by(8).find_map(|ptr| {
+            let assembly = process.read_pointer(ptr, PointerWidth::Bits64).ok()?;
+            if read_name(process, assembly + IL2CPP_ASSEMBLY_NAME).ok()? != name {
+                return None;
+            }
+            Some(Image {
+                addr: process.read_pointer(assembly, PointerWidth::Bits64).ok()?,
+                runtime: Runtime::Il2Cpp {
+                    type_info_table: self.type_info_table,
+                },
+            })
+        })
+    }
+
+    /// Find a class in `Assembly-CSharp`, which is where the game's own code
+    /// is.
+    pub fn get_class(&self, process: &Process, name: &str) -> Option<Class> {
+        self.image(process, "Assembly-CSharp")?
+            .get_class(process, name)
+    }
+}
+
+/// The classes of a loaded assembly, see [`Mono::image`] and
+/// [`Il2Cpp::image`].
+#[derive(Debug, Clone, Copy)]
+pub struct Image {
+    addr: Address,
+    runtime: Runtime,
+}
+
+impl Image {
+    /// Find a class by its name, without the namespace.
+    pub fn get_class(&self, process: &Process, name: &str) -> Option<Class> {
+        match self.runtime {
+            Runtime::Mono(version) => self.get_mono_class(process, version, name),
+            Runtime::Il2Cpp { type_info_table } => {
+                self.get_il2cpp_class(process, type_info_table, name)
+            }
+        }
+    }
+
+    fn get_mono_class(&self, process: &Process, version: MonoVersion, name: &str) -> Option<Class> {
+        let offsets = version.offsets();
+        let cache = self.addr + offsets.image_class_cache;
+        let size: u32 = process.read(cache + offsets.hash_table_size).ok()?;
+        let table = process
+            .read_pointer(cache + offsets.hash_table_table, PointerWidth::Bits64)
+            .ok()?;
+        for i in 0..size as u64 {
+            let mut addr = process
+                .read_pointer(table + i * 8, PointerWidth::Bits64)
+                .ok()?;
+            while addr != 0 {
+                let class = Class {
+                    addr,
+                    runtime: self.runtime,
+                };
+                if class.name(process).ok().as_deref() == Some(name) {
+                    return Some(class);
+                }
+                addr = process
+                    .read_pointer(addr + offsets.class_next_class_cache, PointerWidth::Bits64)
+                    .ok()?;
+            }
+        }
+        None
+    }
+
+    fn get_il2cpp_class(
+        &self,
+        process: &Process,
+        type_info_table: Address,
+        name: &str,
+    ) -> Option<Class> {
+        let count: u32 = process.read(self.addr + IL2CPP_IMAGE_TYPE_COUNT).ok()?;
+        let handle = process
+            .read_pointer(
+                self.addr + IL2CPP_IMAGE_METADATA_HANDLE,
+                PointerWidth::Bits64,
+            )
+            .ok()?;
+        // the image's classes are a slice of the table of every class
+        let start: u32 = process.read(handle).ok()?;
+        let table = process
+            .read_pointer(type_info_table, PointerWidth::Bits64)
+            .ok()?;
+        (start as u64..start as u64 + count as u64).find_map(|i| {
+            let addr = process
+                .read_pointer(table + i * 8, PointerWidth::Bits64)
+                .ok()?;
+            let class = Class {
+                addr,
+                runtime: self.runtime,
+            };
+            // classes the game hasn't used yet are null
+            (addr != 0 && class.name(process).ok()? == name).then_some(class)
+        })
+    }
+}
+
+/// A C# class, see [`Mono::get_class`] and [`Il2Cpp::get_class`].
+#[derive(Debug, Clone, Copy)]
+pub struct Class {
+    addr: Address,
+    runtime: Runtime,
+}
+
+impl Class {
+    /// The address of the runtime's struct for the class.
+    pub fn address(&self) -> Address {
+        self.addr
+    }
+
+    /// The name of the class.
+    pub fn name(&self, process: &Process) -> Result<String> {
+        read_name(process, self.addr + self.runtime.class_offsets().name)
+    }
+
+    /// The namespace the class is in, which is empty for most game code.
+    pub fn namespace(&self, process: &Process) -> Result<String> {
+        read_name(process, self.addr + self.runtime.class_offsets().name_space)
+    }
+
+    /// The class this one inherits from, if any.
+    pub fn parent(&self, process: &Process) -> Option<Class> {
+        let offset = self.runtime.class_offsets().parent;
+        match process.read_pointer(self.addr + offset, PointerWidth::Bits64) {
+            Ok(0) | Err(_) => None,
+            Ok(addr) => Some(Class {
+                addr,
+                runtime: self.runtime,
+            }),
+        }
+    }
+
+    /// The offset of a field from the start of an object of this class (or
+    /// from the class's static data for static fields). Fields inherited from
+    /// parent classes are found too.
+    pub fn field_offset(&self, process: &Process, name: &str) -> Option<u32> {
+        let (_, field) = self.find_field(process, name)?;
+        let offsets = self.runtime.class_offsets();
+        process.read(field + offsets.field_offset).ok()
+    }
+
+    /// Find a field's struct along with the class that declares it, which is
+    /// either this one or one of its parents.
+    fn find_field(&self, process: &Process, name: &str) -> Option<(Class, Address)> {
+        let offsets = self.runtime.class_offsets();
+        let mut class = Some(*self);
+        while let Some(current) = class {
+            let fields = process
+                .read_pointer(current.addr + offsets.fields, PointerWidth::Bits64)
+                .ok()?;
+            // IL2CPP's count is only 16 bits, and Mono's fits in that too
+            let count: u16 = process.read(current.addr + offsets.field_count).ok()?;
+            for i in 0..count as u64 {
+                let field = fields + i * offsets.field_size;
+                if read_name(process, field + offsets.field_name).as_deref() == Ok(name) {
+                    return Some((current, field));
+                }
+            }
+            class = current.parent(process);
+        }
+        None
+    }
+
+    /// Whether the field with its struct at `field` is static and has
+    /// storage, which constants don't.
+    fn is_static(&self, process: &Process, field: Address) -> Result<bool> {
+        const FIELD_ATTRIBUTE_STATIC: u16 = 0x10;
+        const FIELD_ATTRIBUTE_LITERAL: u16 = 0x40;
+        let offsets = self.runtime.class_offsets();
+        let ty = process.read_pointer(field + offsets.field_type, PointerWidth::Bits64)?;
+        // both runtimes keep the attributes right after the type's data pointer
+        let attrs: u16 = process.read(ty + 0x8)?;
+        Ok(attrs & FIELD_ATTRIBUTE_STATIC != 0 && attrs & FIELD_ATTRIBUTE_LITERAL == 0)
+    }
+
+    /// Where the class's static fields are stored. This only exists once
+    /// the game has initialized the class.
+    pub fn static_table(&self, process: &Process) -> Result<Address> {
+        let version = match self.runtime {
+            Runtime::Mono(version) => version,
+            Runtime::Il2Cpp { .. } => {
+                let table = self.addr + IL2CPP_CLASS_STATIC_FIELDS;
+                return match process.read_pointer(table, PointerWidth::Bits64)? {
+                    0 => Err(Error::NullPointer(table)),
+                    addr => Ok(addr),
+                };
+            }
+        };
+        let offsets = version.offsets();
+        let runtime_info =
+            process.read_pointer(self.addr + offsets.class_runtime_info, PointerWidth::Bits64)?;
+        let domain_vtables = process.read_pointer(
+            runtime_info + offsets.runtime_info_domain_vtables,
+            PointerWidth::Bits64,
+        )?;
+        let mut table = domain_vtables + offsets.vtable_vtable;
+        // newer versions keep the static data after the methods in the vtable
+        if version != MonoVersion::V1 {
+            let vtable_size: u32 = process.read(self.addr + offsets.class_vtable_size)?;
+            table += vtable_size as u64 * 8;
+        }
+        match process.read_pointer(table, PointerWidth::Bits64)? {
+            0 => Err(Error::NullPointer(table)),
+            addr => Ok(addr),
+        }
+    }
+
+    /// Read a static field by name. Inherited static fields are read from
+    /// the static data of the parent class that declares them. If the class
+    /// has no static field called `name`, including when there's an instance
+    /// field or a constant by that name, this is an
+    /// [`Error::FieldNotFound`].
+    pub fn static_field<T: Pod>(&self, process: &Process, name: &str) -> Result<T> {
+        let not_found = || Error::FieldNotFound(name.into());
+        let (class, field) = self.find_field(process, name).ok_or_else(not_found)?;
+        if !class.is_static(process, field)? {
+            return Err(not_found());
+        }
+        let offset: u32 = process.read(field + self.runtime.class_offsets().field_offset)?;
+        process.read(class.static_table(process)? + offset as u64)
+    }
+}
+
+/// Read a name through the pointer to it at `addr`.
+fn read_name(process: &Process, addr: Address) -> Result<String> {
+    let ptr = process.read_pointer(addr, PointerWidth::Bits64)?;
+    process.try_read_cstr(ptr, MAX_NAME_LEN)
+}
+
+/// Read the target of a 32-bit offset at `addr` that's relative to the end
+/// of the offset, which is how x86-64 code refers to globals.
+fn read_relative(process: &Process, addr: Address) -> Option<Address> {
+    let rel: i32 = process.read(addr).ok()?;
+    Some((addr + 4).wrapping_add_signed(rel as i64))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mock::{self, Memory};
+
+    const MONO: u64 = 0x1000;
+    const GAME_ASSEMBLY: u64 = 0x1000;
+    const HEAP: u64 = 0x10000;
+
+    /// A `mono.dll` that only exports `mono_assembly_foreach`, and a heap with
+    /// two assemblies whose `Assembly-CSharp` has a `Player` class and a
+    /// `GameManager` class inheriting from it.
+    fn fake_mono() {
+        let mut dll = Memory::new(0x400);
+        dll.pe_export("mono_assembly_foreach", 0x300)
+            .put(0x300, &[0x40, 0x53, 0x48, 0x8B, 0x0D])
+            .put(0x305, &((HEAP - (MONO + 0x309)) as i32).to_le_bytes());
+
+        let mut heap = Memory::new(0x1000);
+        heap.ptr(0x0, HEAP + 0x10)
+            // the list of assemblies
+            .ptr(0x10, HEAP + 0x100)
+            .ptr(0x18, HEAP + 0x20)
+            .ptr(0x20, HEAP + 0x200)
+            .ptr(0x110, HEAP + 0xF00)
+            .ptr(0x210, HEAP + 0xF10)
+            .ptr(0x258, HEAP + 0x300)
+            // the class cache of Assembly-CSharp
+            .put(0x6E8, &2u32.to_le_bytes())
+            .ptr(0x6F0, HEAP + 0x800)
+            .ptr(0x808, HEAP + 0x900)
+            // Player, with a static `lives` field and an instance `speed`
+            .ptr(0x948, HEAP + 0xF40)
+            .ptr(0x9A8, HEAP + 0xC80)
+            .put(0x994, &2u32.to_le_bytes())
+            .ptr(0xC80, HEAP + 0xA80)
+            .ptr(0xC88, HEAP + 0xF70)
+            .put(0xC98, &4u32.to_le_bytes())
+            .ptr(0xCA0, HEAP + 0xA90)
+            .ptr(0xCA8, HEAP + 0xF80)
+            .put(0xCB8, &0x10u32.to_le_bytes())
+            .ptr(0xA00, HEAP + 0xB00)
+            .ptr(0x9F8, HEAP + 0xD90)
+            .ptr(0xD98, HEAP + 0xD10)
+            .ptr(0xD58, HEAP + 0xE40)
+            // GameManager, with `instance` and `deathCount`
+            .ptr(0xB48, HEAP + 0xF30)
+            .ptr(0xB30, HEAP + 0x900)
+            .ptr(0xBA8, HEAP + 0xC40)
+            .put(0xB94, &2u32.to_le_bytes())
+            .ptr(0xBF8, HEAP + 0xD80)
+            .ptr(0xC40, HEAP + 0xA80)
+            .ptr(0xC48, HEAP + 0xF50)
+            .ptr(0xC60, HEAP + 0xA80)
+            .ptr(0xC68, HEAP + 0xF60)
+            .put(0xC78, &8u32.to_le_bytes())
+            .ptr(0xD88, HEAP + 0xD00)
+            .ptr(0xD48, HEAP + 0xE00)
+            // the types of static and instance fields
+            .put(0xA88, &0x16u16.to_le_bytes())
+            .put(0xA98, &0x6u16.to_le_bytes())
+            // GameManager's static data, which doesn't hold `lives`
+            .put(0xE04, &(-1i32).to_le_bytes())
+            .put(0xE08, &42i32.to_le_bytes())
+            // Player's static data
+            .put(0xE44, &3i32.to_le_bytes())
+            .put(0xF00, b"mscorlib\0")
+            .put(0xF10, b"Assembly-CSharp\0")
+            .put(0xF30, b"GameManager\0")
+            .put(0xF40, b"Player\0")
+            .put(0xF50, b"instance\0")
+            .put(0xF60, b"deathCount\0")
+            .put(0xF70, b"lives\0")
+            .put(0xF80, b"speed\0");
+
+        mock::with_host(|h| {
+            let p = h.spawn(1, "Game.exe");
+            p.load("mono.dll", MONO, dll.0);
+            p.map(HEAP, heap.0);
+        });
+    }
+
+    /// A `GameAssembly.dll` with the code that refers to IL2CPP's lists, and a
+    /// heap laid out like [`fake_mono`]'s but with IL2CPP's structs.
+    fn fake_il2cpp() {
+        let mut dll = Memory::new(0x400);
+        dll.put(
+            0x100,
+            &[0x48, 0xFF, 0xC5, 0x80, 0x3C, 0x1E, 0x00, 0x75, 0xF2],
+        )
+        .put(0x109, &[0x48, 0x8B, 0x1D])
+        .put(
+            0x10C,
+            &((HEAP - (GAME_ASSEMBLY + 0x110)) as i32).to_le_bytes(),
+        )
+        .put(
+            0x200,
+            &((HEAP + 0x10 - (GAME_ASSEMBLY + 0x204)) as i32).to_le_bytes(),
+        )
+        .put(
+            0x204,
+            &[0x48, 0x83, 0x3C, 0xC8, 0x00, 0x75, 0x10, 0x8B, 0xCB, 0xE8],
+        );
+
+        let mut heap = Memory::new(0x1000);
+        // the vector of assemblies, then the table of every class
+        heap.ptr(0x0, HEAP + 0x100)
+            .ptr(0x8, HEAP + 0x110)
+            .ptr(0x10, HEAP + 0x200)
+            .ptr(0x100, HEAP + 0x300)
+            .ptr(0x108, HEAP + 0x340)
+            .ptr(0x318, HEAP + 0xF00)
+            .ptr(0x340, HEAP + 0x440)
+            .ptr(0x358, HEAP + 0xF10)
+            // Assembly-CSharp's classes start at index 1, after mscorlib's
+            .put(0x458, &3u32.to_le_bytes())
+            .ptr(0x468, HEAP + 0x480)
+            .put(0x480, &1u32.to_le_bytes())
+            .ptr(0x208, HEAP + 0x500)
+            .ptr(0x210, HEAP + 0x700)
+            // Player, with a static `lives` field and an instance `speed`
+            .ptr(0x510, HEAP + 0xF40)
+            .ptr(0x580, HEAP + 0x900)
+            .put(0x620, &2u16.to_le_bytes())
+            .ptr(0x5B8, HEAP + 0xE40)
+            .ptr(0x900, HEAP + 0xF70)
+            .ptr(0x908, HEAP + 0xA00)
+            .put(0x918, &4u32.to_le_bytes())
+            .ptr(0x920, HEAP + 0xF80)
+            .ptr(0x928, HEAP + 0xA10)
+            .put(0x938, &0x10u32.to_le_bytes())
+            // GameManager, with a static `deathCount`
+            .ptr(0x710, HEAP + 0xF30)
+            .ptr(0x758, HEAP + 0x500)
+            .ptr(0x780, HEAP + 0x880)
+            .put(0x820, &1u16.to_le_bytes())
+            .ptr(0x7B8, HEAP + 0xE00)
+            .ptr(0x880, HEAP + 0xF60)
+            .ptr(0x888, HEAP + 0xA00)
+            .put(0x898, &8u32.to_le_bytes())
+            // the types of static and instance fields
+            .put(0xA08, &0x16u16.to_le_bytes())
+            .put(0xA18, &0x6u16.to_le_bytes())
+            .put(0xE08, &42i32.to_le_bytes())
+            .put(0xE44, &3i32.to_le_bytes())
+            .put(0xF00, b"mscorlib\0")
+            .put(0xF10, b"Assembly-CSharp\0")
+            .put(0xF30, b"GameManager\0")
+            .put(0xF40, b"Player\0")
+            .put(0xF60, b"deathCount\0")
+            .put(0xF70, b"lives\0")
+            .put(0xF80, b"speed\0");
+
+        mock::with_host(|h| {
+            let p = h.spawn(1, "Game.exe");
+            p.load("GameAssembly.dll", GAME_ASSEMBLY, dll.0);
+            p.map(HEAP, heap.0);
+        });
+    }
+
+    #[test]
+    fn reads_static_fields() {
+        fake_mono();
+        let p = Process::attach("Game.exe").unwrap();
+        let mono = Mono::attach(&p).unwrap();
+        assert_eq!(mono.version(), MonoVersion::V1);
+        assert!(mono.image(&p, "mscorlib").is_some());
+        assert!(mono.image(&p, "UnityEngine").is_none());
+        assert!(mono.get_class(&p, "Enemy").is_none());
+
+        let game_manager = mono.get_class(&p, "GameManager").unwrap();
+        assert_eq!(game_manager.name(&p).as_deref(), Ok("GameManager"));
+        assert_eq!(game_manager.parent(&p).unwrap().address(), HEAP + 0x900);
+        assert_eq!(game_manager.field_offset(&p, "deathCount"), Some(8));
+        assert_eq!(game_manager.field_offset(&p, "lives"), Some(4));
+        assert_eq!(game_manager.static_field::<i32>(&p, "deathCount"), Ok(42));
+        assert_eq!(game_manager.static_field::<i32>(&p, "lives"), Ok(3));
+        let player = game_manager.parent(&p).unwrap();
+        assert_eq!(player.static_table(&p), Ok(HEAP + 0xE40));
+        assert_eq!(player.static_field::<i32>(&p, "lives"), Ok(3));
+        assert_eq!(player.field_offset(&p, "speed"), Some(0x10));
+        assert_eq!(
+            game_manager.static_field::<i32>(&p, "speed"),
+            Err(Error::FieldNotFound("speed".into()))
+        );
+        assert_eq!(
+            game_manager.static_field::<i32>(&p, "health"),
+            Err(Error::FieldNotFound("health".into()))
+        );
+    }
+
+    #[test]
+    fn reads_il2cpp_static_fields() {
+        fake_il2cpp();
+        let p = Process::attach("Game.exe").unwrap();
+        assert!(Mono::attach(&p).is_none());
+        let il2cpp = Il2Cpp::attach(&p).unwrap();
+        assert!(il2cpp.image(&p, "mscorlib").is_some());
+        assert!(il2cpp.image(&p, "UnityEngine").is_none());
+        assert!(il2cpp.get_class(&p, "Enemy").is_none());
+
+        let game_manager = il2cpp.get_class(&p, "GameManager").unwrap();
+        assert_eq!(game_manager.address(), HEAP + 0x700);
+        assert_eq!(game_manager.static_field::<i32>(&p, "deathCount"), Ok(42));
+        assert_eq!(game_manager.static_field::<i32>(&p, "lives"), Ok(3));
+        assert_eq!(game_manager.field_offset(&p, "speed"), Some(0x10));
+        assert_eq!(
+            game_manager.static_field::<i32>(&p, "speed"),
+            Err(Error::FieldNotFound("speed".into()))
+        );
+        let player = game_manager.parent(&p).unwrap();
+        assert_eq!(player.name(&p).as_deref(), Ok("Player"));
+        assert_eq!(player.static_table(&p), Ok(HEAP + 0xE40));
+    }
+}
@@ -0,0 +1,353 @@
+//! Reading the state of Unreal Engine 4 and 5 games through the engine's
+//! reflection data.
+//!
+//! Every object in an Unreal game has a name and a class, and every class
+//! lists its properties by name with their offsets. [`Unreal`] finds the
+//! globals this all hangs off of (`GWorld`, `GNames` and `GObjects`) so
+//! splitters can read properties by name instead of with pointer paths that
+//! break with every update of the game:
+//!
+//! ```no_run
+//! # use livesplit_wrapper::{unreal::Unreal, Process};
+//! # fn f(process: &Process) -> Option<()> {
+//! let unreal = Unreal::attach(process, "Game-Win64-Shipping.exe")?;
+//! let world = unreal.world(process).ok()?;
+//! // the world is named after the level that's loaded
+//! let level = world.name(process, &unreal).ok()?;
+//! let game_mode = world
+//!     .read_object(process, &unreal, "AuthorityGameMode")
+//!     .ok()?;
+//! # Some(())
+//! # }
+//! ```
+//!
+//! The offsets used are the ones from 64-bit UE 4.25 and newer, which is when
+//! properties got their own `FProperty` type. Older games or ones with a
+//! customized engine won't work with this.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+
+use crate::{Address, Error, Pod, PointerWidth, Process, Result, Zeroable};
+
+const UOBJECT_CLASS: u64 = 0x10;
+const UOBJECT_NAME: u64 = 0x18;
+const USTRUCT_SUPER: u64 = 0x40;
+const USTRUCT_CHILD_PROPERTIES: u64 = 0x50;
+const FFIELD_NEXT: u64 = 0x20;
+const FFIELD_NAME: u64 = 0x28;
+const FPROPERTY_OFFSET: u64 = 0x4C;
+const NAME_POOL_BLOCKS: u64 = 0x10;
+const OBJECT_ARRAY_COUNT: u64 = 0x14;
+const OBJECT_ITEM_SIZE: u64 = 0x18;
+const OBJECTS_PER_CHUNK: u64 = 0x10000;
+
+/// Signatures for an instruction that refers to each global with a 32-bit
+/// relative address, and how far into the match the address is.
+const GWORLD: &[(&str, u64)] = &[("48 8B 1D ?? ?? ?? ?? 48 85 DB 74 ?? 41 B0 01", 3)];
+const GNAMES: &[(&str, u64)] = &[
+    (
+        "48 8D 0D ?? ?? ?? ?? E8 ?? ?? ?? ?? C6 05 ?? ?? ?? ?? 01 0F 10 03",
+        3,
+    ),
+    ("74 09 48 8D 15 ?? ?? ?? ?? EB 16", 5),
+];
+const GOBJECTS: &[(&str, u64)] = &[("48 8B 05 ?? ?? ?? ?? 48 8B 0C C8 48 8D 04 D1", 3)];
+
+/// A name as Unreal stores it, which is an index into the table of every name
+/// that's used by the game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Pod, Zeroable)]
+#[repr(C)]
+pub struct FName {
+    /// Which entry in `GNames` the name is.
+    pub index: u32,
+    /// A number that goes after the name, plus one, or 0 if there isn't one.
+    pub number: u32,
+}
+
+/// The globals of an Unreal Engine game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Unreal {
+    gworld: Address,
+    gnames: Address,
+    gobjects: Address,
+}
+
+impl Unreal {
+    /// Use globals that were found some other way, for games where the
+    /// built-in signatures don't match. `gworld` is the address of the
+    /// pointer to the current `UWorld`, `gnames` is the address of the
+    /// `FNamePool`, and `gobjects` is the address of the chunked object
+    /// array inside `FUObjectArray`.
+    pub const fn new(gworld: Address, gnames: Address, gobjects: Address) -> Self {
+        Self {
+            gworld,
+            gnames,
+            gobjects,
+        }
+    }
+
+    /// Find the globals by scanning `module`, which is usually the game's
+    /// main executable. Returns `None` if any of them can't be found.
+    pub fn attach(process: &Process, module: &str) -> Option<Self> {
+        let find = |signatures: &[(&str, u64)]| {
+            signatures.iter().find_map(|&(pattern, offset)| {
+                let found = process.scan_signature(module, pattern)? + offset;
+                let rel: i32 = process.read(found).ok()?;
+                Some((found + 4).wrapping_add_signed(rel as i64))
+            })
+        };
+        Some(Self::new(find(GWORLD)?, find(GNAMES)?, find(GOBJECTS)?))
+    }
+
+    /// The address of the pointer to the current world.
+    pub fn gworld(&self) -> Address {
+        self.gworld
+    }
+
+    /// The address of the table of names.
+    pub fn gnames(&self) -> Address {
+        self.gnames
+    }
+
+    /// The address of the array of every object.
+    pub fn gobjects(&self) -> Address {
+        self.gobjects
+    }
+
+    /// The world that's loaded right now, whose name is the name of the
+    /// level.
+    pub fn world(&self, process: &Process) -> Result<UObject> {
+        UObject::at(process, self.gworld)
+    }
+
+    /// Look up the text of a name.
+    pub fn fname(&self, process: &Process, name: FName) -> Result<String> {
+        let block = name.index as u64 >> 16;
+        let offset = (name.index as u64 & 0xFFFF) * 2;
+        let block = process.read_pointer(
+            self.gnames + NAME_POOL_BLOCKS + block * 8,
+            PointerWidth::Bits64,
+        )?;
+        let entry = block + offset;
+        let header: u16 = process.read(entry)?;
+        let len = (header >> 6) as usize;
+        let text = if header & 1 == 0 {
+            let mut buf = vec![0; len];
+            process.read_into_buf(entry + 2, &mut buf)?;
+            String::from_utf8(buf).map_err(|_| Error::InvalidString(entry))?
+        } else {
+            let mut buf = vec![0u16; len];
+            process.read_into_buf(entry + 2, bytemuck::cast_slice_mut(&mut buf))?;
+            String::from_utf16(&buf).map_err(|_| Error::InvalidString(entry))?
+        };
+        Ok(match name.number {
+            0 => text,
+            n => format!("{text}_{}", n - 1),
+        })
+    }
+
+    /// Read an [`FName`] at `addr` and look up its text.
+    pub fn read_fname(&self, process: &Process, addr: Address) -> Result<String> {
+        self.fname(process, process.read(addr)?)
+    }
+
+    /// The object at `index` in `GObjects`, or `None` if that slot is empty.
+    pub fn object(&self, process: &Process, index: u32) -> Result<Option<UObject>> {
+        let index = index as u64;
+        let chunks = process.read_pointer(self.gobjects, PointerWidth::Bits64)?;
+        let chunk =
+            process.read_pointer(chunks + index / OBJECTS_PER_CHUNK * 8, PointerWidth::Bits64)?;
+        let item = chunk + index % OBJECTS_PER_CHUNK * OBJECT_ITEM_SIZE;
+        match process.read_pointer(item, PointerWidth::Bits64)? {
+            0 => Ok(None),
+            addr => Ok(Some(UObject(addr))),
+        }
+    }
+
+    /// Find the first object in `GObjects` called `name`. This looks through
+    /// every object in the game, so only do it once and keep the result
+    /// around.
+    pub fn find_object(&self, process: &Process, name: &str) -> Option<UObject> {
+        let count: u32 = process.read(self.gobjects + OBJECT_ARRAY_COUNT).ok()?;
+        (0..count).find_map(|i| {
+            let object = self.object(process, i).ok()??;
+            (object.name(process, self).ok()? == name).then_some(object)
+        })
+    }
+}
+
+/// An object in an Unreal game, like an actor or the world.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UObject(Address);
+
+impl UObject {
+    /// The object that `ptr` points to.
+    pub fn at(process: &Process, ptr: Address) -> Result<Self> {
+        match process.read_pointer(ptr, PointerWidth::Bits64)? {
+            0 => Err(Error::NullPointer(ptr)),
+            addr => Ok(Self(addr)),
+        }
+    }
+
+    /// The address of the object.
+    pub fn address(&self) -> Address {
+        self.0
+    }
+
+    /// The name of the object.
+    pub fn name(&self, process: &Process, unreal: &Unreal) -> Result<String> {
+        unreal.read_fname(process, self.0 + UOBJECT_NAME)
+    }
+
+    /// The class of the object, which is an object itself.
+    pub fn class(&self, process: &Process) -> Result<UObject> {
+        UObject::at(process, self.0 + UOBJECT_CLASS)
+    }
+
+    /// The offset of a property of the object's class (or one of the classes
+    /// it inherits from).
+    pub fn property_offset(&self, process: &Process, unreal: &Unreal, name: &str) -> Option<u32> {
+        let mut class = self.class(process).ok();
+        while let Some(current) = class {
+            let mut property = process
+                .read_pointer(current.0 + USTRUCT_CHILD_PROPERTIES, PointerWidth::Bits64)
+                .ok()?;
+            while property != 0 {
+                if unreal
+                    .read_fname(process, property + FFIELD_NAME)
+                    .as_deref()
+                    == Ok(name)
+                {
+                    return process.read(property + FPROPERTY_OFFSET).ok();
+                }
+                property = process
+                    .read_pointer(property + FFIELD_NEXT, PointerWidth::Bits64)
+                    .ok()?;
+            }
+            class = UObject::at(process, current.0 + USTRUCT_SUPER).ok();
+        }
+        None
+    }
+
+    /// Read a property by name. If the object's class has no property called
+    /// `name` this is an [`Error::FieldNotFound`].
+    pub fn read<T: Pod>(&self, process: &Process, unreal: &Unreal, name: &str) -> Result<T> {
+        let offset = self
+            .property_offset(process, unreal, name)
+            .ok_or_else(|| Error::FieldNotFound(name.into()))?;
+        process.read(self.0 + offset as u64)
+    }
+
+    /// Follow a property that points to another object. Like with
+    /// [`read`](UObject::read), a missing property is an
+    /// [`Error::FieldNotFound`].
+    pub fn read_object(&self, process: &Process, unreal: &Unreal, name: &str) -> Result<UObject> {
+        let offset = self
+            .property_offset(process, unreal, name)
+            .ok_or_else(|| Error::FieldNotFound(name.into()))?;
+        UObject::at(process, self.0 + offset as u64)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mock::{self, Memory};
+
+    trait NamePool {
+        /// An `FNamePool` entry, which starts with its length and whether
+        /// it's wide.
+        fn name(&mut self, offset: u64, name: &str) -> &mut Self;
+    }
+
+    impl NamePool for Memory {
+        fn name(&mut self, offset: u64, name: &str) -> &mut Self {
+            self.put(offset, &((name.len() as u16) << 6).to_le_bytes())
+                .put(offset + 2, name.as_bytes())
+        }
+    }
+
+    const EXE: u64 = 0x1000;
+    const HEAP: u64 = 0x10000;
+
+    /// A game with a world called `Forest` holding a game mode, whose class
+    /// inherits a `Deaths` property.
+    fn fake_game() {
+        let rel = |at: u64, target: u64| ((target - (EXE + at + 4)) as i32).to_le_bytes();
+        let mut exe = Memory::new(0x100);
+        exe.put(0x10, &[0x48, 0x8B, 0x1D])
+            .put(0x13, &rel(0x13, HEAP))
+            .put(0x17, &[0x48, 0x85, 0xDB, 0x74, 0x00, 0x41, 0xB0, 0x01])
+            .put(0x30, &[0x74, 0x09, 0x48, 0x8D, 0x15])
+            .put(0x35, &rel(0x35, HEAP + 0x100))
+            .put(0x39, &[0xEB, 0x16])
+            .put(0x50, &[0x48, 0x8B, 0x05])
+            .put(0x53, &rel(0x53, HEAP + 0x200))
+            .put(0x57, &[0x48, 0x8B, 0x0C, 0xC8, 0x48, 0x8D, 0x04, 0xD1]);
+
+        let mut heap = Memory::new(0x1000);
+        let fname = |index: u32, number: u32| {
+            let mut bytes = [0; 8];
+            bytes[..4].copy_from_slice(&index.to_le_bytes());
+            bytes[4..].copy_from_slice(&number.to_le_bytes());
+            bytes
+        };
+        heap.ptr(0x0, HEAP + 0x400)
+            // the name pool, with one block of names
+            .ptr(0x110, HEAP + 0x800)
+            .name(0x800, "Forest")
+            .name(0x810, "AuthorityGameMode")
+            .name(0x830, "Deaths")
+            .name(0x840, "GameMode")
+            // the object array, with the world and game mode in one chunk
+            .ptr(0x200, HEAP + 0x300)
+            .put(0x214, &2u32.to_le_bytes())
+            .ptr(0x300, HEAP + 0x380)
+            .ptr(0x380, HEAP + 0x400)
+            .ptr(0x398, HEAP + 0x500)
+            // the world, pointing to the game mode
+            .ptr(0x410, HEAP + 0x600)
+            .put(0x418, &fname(0, 0))
+            .ptr(0x430, HEAP + 0x500)
+            .ptr(0x650, HEAP + 0x700)
+            .put(0x728, &fname(8, 0))
+            .put(0x74C, &0x30u32.to_le_bytes())
+            // the game mode, whose class gets `Deaths` from its parent
+            .ptr(0x510, HEAP + 0x680)
+            .put(0x518, &fname(0x20, 2))
+            .put(0x530, &7u32.to_le_bytes())
+            .ptr(0x6C0, HEAP + 0xA00)
+            .ptr(0xA50, HEAP + 0x780)
+            .put(0x7A8, &fname(0x18, 0))
+            .put(0x7CC, &0x30u32.to_le_bytes());
+
+        mock::with_host(|h| {
+            let p = h.spawn(1, "Game.exe");
+            p.load("Game.exe", EXE, exe.0);
+            p.map(HEAP, heap.0);
+        });
+    }
+
+    #[test]
+    fn reads_properties_by_name() {
+        fake_game();
+        let p = Process::attach("Game.exe").unwrap();
+        let unreal = Unreal::attach(&p, "Game.exe").unwrap();
+        assert_eq!(unreal, Unreal::new(HEAP, HEAP + 0x100, HEAP + 0x200));
+
+        let world = unreal.world(&p).unwrap();
+        assert_eq!(world.address(), HEAP + 0x400);
+        assert_eq!(world.name(&p, &unreal).as_deref(), Ok("Forest"));
+        let game_mode = world.read_object(&p, &unreal, "AuthorityGameMode").unwrap();
+        assert_eq!(game_mode.name(&p, &unreal).as_deref(), Ok("GameMode_1"));
+        assert_eq!(game_mode.read::<u32>(&p, &unreal, "Deaths"), Ok(7));
+        assert_eq!(
+            game_mode.read::<u32>(&p, &unreal, "Lives"),
+            Err(Error::FieldNotFound("Lives".into()))
+        );
+        assert_eq!(unreal.find_object(&p, "GameMode_1"), Some(game_mode));
+        assert_eq!(unreal.find_object(&p, "Menu"), None);
+    }
+}
@@ -0,0 +1,187 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{Display, Write};
+use core::time::Duration;
+
+use crate::{fmt, HostFunctions};
+
+/// A single variable that's only sent to the host when its value changes, so
+/// it can be set every tick. Values are formatted into a fixed size buffer
+/// like with [`set_variable_display`](HostFunctions::set_variable_display),
+/// so they're cut off after 64 bytes.
+///
+/// ```no_run
+/// # use livesplit_wrapper::{Host, Variable};
+/// # let deaths = 3;
+/// let mut variable = Variable::new("deaths");
+/// variable.set_display(deaths, &Host);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Variable {
+    key: &'static str,
+    sent: Option<String>,
+}
+
+impl Variable {
+    /// A variable called `key` that hasn't been sent yet.
+    pub const fn new(key: &'static str) -> Self {
+        Self { key, sent: None }
+    }
+
+    /// The name of the variable.
+    pub fn key(&self) -> &'static str {
+        self.key
+    }
+
+    /// Send `value` unless it's what was last sent. Returns whether it was
+    /// sent.
+    pub fn set(&mut self, value: &str, host: &impl HostFunctions) -> bool {
+        if self.sent.as_deref() == Some(value) {
+            return false;
+        }
+        host.set_variable(self.key, value);
+        let sent = self.sent.get_or_insert_with(String::new);
+        sent.clear();
+        sent.push_str(value);
+        true
+    }
+
+    /// Send anything that implements [`Display`], unless it formats the same
+    /// as what was last sent.
+    pub fn set_display<V: Display>(&mut self, value: V, host: &impl HostFunctions) -> bool {
+        let mut buf = fmt::FixedBuf::<64>::new();
+        write!(buf, "{value}").ok();
+        self.set(buf.as_str(), host)
+    }
+
+    /// Send a float rounded to `precision` digits after the decimal point.
+    pub fn set_float(&mut self, value: f64, precision: usize, host: &impl HostFunctions) -> bool {
+        self.set_display(format_args!("{value:.precision$}"), host)
+    }
+
+    /// Send a duration formatted like
+    /// [`set_variable_duration`](HostFunctions::set_variable_duration) does.
+    pub fn set_duration(&mut self, value: Duration, host: &impl HostFunctions) -> bool {
+        self.set_display(fmt::Clock(value), host)
+    }
+}
+
+/// Collects variables to set and sends them all at once, skipping any whose
+/// value hasn't changed since it was last sent. Splitters that show a lot of
+/// values can set all of them every tick without worrying about how many
+/// actually changed.
+///
+/// ```no_run
+/// # use livesplit_wrapper::{Host, VariableBatch};
+/// # let (mut batch, deaths, level) = (VariableBatch::new(), 3, "Old Site");
+/// batch.set_display("deaths", deaths);
+/// batch.set("level", level);
+/// batch.flush(&Host);
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct VariableBatch {
+    pending: Vec<(String, String)>,
+    sent: Vec<(String, String)>,
+}
+
+impl VariableBatch {
+    /// Create an empty batch.
+    pub const fn new() -> Self {
+        Self {
+            pending: Vec::new(),
+            sent: Vec::new(),
+        }
+    }
+
+    /// Queue a variable to be set on the next flush, replacing any value that
+    /// was already queued for it.
+    pub fn set(&mut self, key: &str, value: &str) {
+        let value_buf = self.slot(key);
+        value_buf.clear();
+        value_buf.push_str(value);
+    }
+
+    /// Queue a variable to be set to anything that implements [`Display`].
+    pub fn set_display<V: Display>(&mut self, key: &str, value: V) {
+        let value_buf = self.slot(key);
+        value_buf.clear();
+        // writing to a `String` can't fail
+        write!(value_buf, "{value}").ok();
+    }
+
+    /// Send every queued variable that's different from what was last sent
+    /// for it, and return how many were sent.
+    pub fn flush(&mut self, host: &impl HostFunctions) -> usize {
+        let mut count = 0;
+        for (key, value) in self.pending.drain(..) {
+            let i = match self.sent.iter().position(|(k, _)| *k == key) {
+                Some(i) if self.sent[i].1 == value => continue,
+                Some(i) => {
+                    self.sent[i].1 = value;
+                    i
+                }
+                None => {
+                    self.sent.push((key, value));
+                    self.sent.len() - 1
+                }
+            };
+            let (key, value) = &self.sent[i];
+            host.set_variable(key, value);
+            count += 1;
+        }
+        count
+    }
+
+    fn slot(&mut self, key: &str) -> &mut String {
+        let i = match self.pending.iter().position(|(k, _)| k == key) {
+            Some(i) => i,
+            None => {
+                self.pending.push((key.into(), String::new()));
+                self.pending.len() - 1
+            }
+        };
+        &mut self.pending[i].1
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{mock, Host};
+
+    #[test]
+    fn variable_skips_repeats() {
+        let mut igt = Variable::new("igt");
+        assert!(igt.set_duration(Duration::from_secs(61), &Host));
+        assert!(!igt.set_duration(Duration::from_secs(61), &Host));
+        assert!(!igt.set("01:01.000", &Host));
+        assert!(igt.set_float(1.0, 1, &Host));
+        mock::with_host(|h| assert_eq!(h.variables.len(), 2));
+        assert_eq!(crate::testing::variable("igt").as_deref(), Some("1.0"));
+    }
+
+    #[test]
+    fn unchanged_variables_are_skipped() {
+        let mut batch = VariableBatch::new();
+        batch.set("level", "Prologue");
+        batch.set_display("deaths", 0);
+        assert_eq!(batch.flush(&Host), 2);
+
+        batch.set("level", "Prologue");
+        batch.set_display("deaths", 1);
+        batch.set_display("deaths", 2);
+        assert_eq!(batch.flush(&Host), 1);
+        assert_eq!(batch.flush(&Host), 0);
+        mock::with_host(|h| {
+            let sent: Vec<_> = h
+                .variables
+                .iter()
+                .map(|(k, v)| (k.as_str(), v.as_str()))
+                .collect();
+            assert_eq!(
+                sent,
+                [("level", "Prologue"), ("deaths", "0"), ("deaths", "2")]
+            );
+        });
+    }
+}
@@ -0,0 +1,154 @@
+use alloc::vec::Vec;
+
+use crate::{Error, Module, Process, Result};
+
+/// Figures out which release of a game is running from its module, so the
+/// right set of addresses can be picked for it. Versions can be told apart by
+/// the size of the module, which is cheap to check, or by a hash of its
+/// contents for releases that happen to be the same size.
+///
+/// ```no_run
+/// # use livesplit_wrapper::{Process, VersionDetector};
+/// # fn f(process: &Process) {
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// enum Version {
+///     Steam,
+///     Itch,
+///     Patch2,
+/// }
+///
+/// let detector = VersionDetector::new()
+///     .size(0x1A4000, Version::Steam)
+///     .size(0x1A3000, Version::Itch)
+///     .hash(0x8C3B_71E0_92D4_4F15, Version::Patch2);
+/// match detector.detect(process) {
+///     Some(version) => log::info!("found {version:?}"),
+///     None => log::warn!(
+///         "unknown version, hash {:x?}",
+///         VersionDetector::<Version>::hash_module(process, process.name()),
+///     ),
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct VersionDetector<V> {
+    module: Option<&'static str>,
+    sizes: Vec<(u64, V)>,
+    hashes: Vec<(u64, V)>,
+}
+
+impl<V: Copy> VersionDetector<V> {
+    /// Create a detector that looks at the process's main module, with no
+    /// versions registered yet.
+    pub const fn new() -> Self {
+        Self {
+            module: None,
+            sizes: Vec::new(),
+            hashes: Vec::new(),
+        }
+    }
+
+    /// Look at the module called `name` instead of the main module, for
+    /// games where the interesting code is in a DLL.
+    pub fn module(mut self, name: &'static str) -> Self {
+        self.module = Some(name);
+        self
+    }
+
+    /// Detect `version` when the module is `size` bytes.
+    pub fn size(mut self, size: u64, version: V) -> Self {
+        self.sizes.push((size, version));
+        self
+    }
+
+    /// Detect `version` when the module's contents hash to `hash`, see
+    /// [`hash_module`](VersionDetector::hash_module).
+    pub fn hash(mut self, hash: u64, version: V) -> Self {
+        self.hashes.push((hash, version));
+        self
+    }
+
+    /// Work out which version is running. Sizes are checked first, and the
+    /// module is only hashed if none of them match and there are hashes to
+    /// check. Returns `None` if the module isn't loaded or nothing matches.
+    pub fn detect(&self, process: &Process) -> Option<V> {
+        let module = process.module_range(self.module.unwrap_or(process.name()))?;
+        let by_size = self.sizes.iter().find(|(size, _)| *size == module.size);
+        if let Some((_, version)) = by_size {
+            return Some(*version);
+        }
+        if self.hashes.is_empty() {
+            return None;
+        }
+        let hash = hash(process, &module).ok()?;
+        self.hashes
+            .iter()
+            .find(|(h, _)| *h == hash)
+            .map(|(_, version)| *version)
+    }
+
+    /// Hash the contents of a module the same way
+    /// [`detect`](VersionDetector::detect) does, for finding out what to
+    /// register a new version with. This reads the whole module, so it's slow
+    /// for big ones.
+    pub fn hash_module(process: &Process, name: &str) -> Option<u64> {
+        hash(process, &process.module_range(name)?).ok()
+    }
+}
+
+impl<V: Copy> Default for VersionDetector<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 64-bit FNV-1a of the module's bytes.
+fn hash(process: &Process, module: &Module) -> Result<u64> {
+    const CHUNK_SIZE: usize = 0x10000;
+    let mut hash = 0xCBF2_9CE4_8422_2325u64;
+    // a module can be bigger than a 32-bit splitter can address
+    let len = usize::try_from(module.size).map_err(|_| Error::InvalidCount(module.size))?;
+    for chunk in process.read_chunks(module.base, len, CHUNK_SIZE) {
+        for byte in chunk? {
+            hash = (hash ^ byte as u64).wrapping_mul(0x100_0000_01B3);
+        }
+    }
+    Ok(hash)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mock;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Version {
+        Old,
+        New,
+        Patched,
+    }
+
+    #[test]
+    fn detects_by_size_then_hash() {
+        mock::with_host(|h| {
+            let p = h.spawn(1, "Game.exe");
+            p.load("Game.exe", 0x1000, *b"abc");
+            p.load("engine.dll", 0x2000, [0; 0x20]);
+        });
+        let p = Process::attach("Game.exe").unwrap();
+        // FNV-1a of "abc"
+        assert_eq!(
+            VersionDetector::<Version>::hash_module(&p, "Game.exe"),
+            Some(0xE71F_A219_0541_574B)
+        );
+        let detector = VersionDetector::new()
+            .size(0x20, Version::Old)
+            .hash(0xE71F_A219_0541_574B, Version::Patched);
+        assert_eq!(detector.detect(&p), Some(Version::Patched));
+        let detector = detector.module("engine.dll");
+        assert_eq!(detector.detect(&p), Some(Version::Old));
+        let detector = VersionDetector::new().size(3, Version::New);
+        assert_eq!(detector.detect(&p), Some(Version::New));
+        assert_eq!(detector.module("other.dll").detect(&p), None);
+    }
+}
@@ -0,0 +1,127 @@
+use alloc::vec::Vec;
+use core::mem;
+
+use crate::{Address, Error, Pod, Process, Result};
+
+/// A table of named fields in a struct in the game's memory, so the offsets
+/// are written down once and fields are read by name. When the game updates
+/// and the layout changes only the table needs fixing.
+///
+/// ```no_run
+/// # use livesplit_wrapper::{Process, StructView};
+/// # fn f(process: &Process) -> livesplit_wrapper::Result<()> {
+/// let player = StructView::new(0x1000)
+///     .with_field("health", 0x10, 4)
+///     .with_field("x", 0x20, 4)
+///     .with_field("dead", 0x28, 1);
+/// let health: u32 = player.field(process, "health")?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct StructView {
+    base: Address,
+    fields: Vec<(&'static str, u64, usize)>,
+}
+
+impl StructView {
+    /// Create a view of the struct at `base` with no fields yet.
+    pub const fn new(base: Address) -> Self {
+        Self {
+            base,
+            fields: Vec::new(),
+        }
+    }
+
+    /// Add a field that's `size` bytes long at `offset` from the start of the
+    /// struct, for building the table in one expression.
+    pub fn with_field(mut self, name: &'static str, offset: u64, size: usize) -> Self {
+        self.insert(name, offset, size);
+        self
+    }
+
+    /// Add a field to an existing view, like one whose layout depends on the
+    /// game's version. A field with the same name as an earlier one is never
+    /// read, since lookups find the first match.
+    pub fn insert(&mut self, name: &'static str, offset: u64, size: usize) {
+        self.fields.push((name, offset, size));
+    }
+
+    /// The address of the struct.
+    pub fn base(&self) -> Address {
+        self.base
+    }
+
+    /// Point the view at another struct with the same layout, like the next
+    /// element of an array or one found with a
+    /// [`DeepPointer`](crate::DeepPointer).
+    pub fn set_base(&mut self, base: Address) {
+        self.base = base;
+    }
+
+    /// The address of the named field, or `None` if there's no field with
+    /// that name.
+    pub fn address(&self, name: &str) -> Option<Address> {
+        self.get(name).map(|&(_, offset, _)| self.base + offset)
+    }
+
+    /// Read the named field. Reading a field that isn't in the table gives an
+    /// [`Error::FieldNotFound`], and reading it as a type that's a different
+    /// size than the field gives an [`Error::WrongFieldSize`].
+    pub fn field<T: Pod>(&self, process: &Process, name: &str) -> Result<T> {
+        let &(_, offset, size) = self
+            .get(name)
+            .ok_or_else(|| Error::FieldNotFound(name.into()))?;
+        if size != mem::size_of::<T>() {
+            return Err(Error::WrongFieldSize {
+                name: name.into(),
+                size,
+            });
+        }
+        process.read(self.base + offset)
+    }
+
+    fn get(&self, name: &str) -> Option<&(&'static str, u64, usize)> {
+        self.fields.iter().find(|(n, ..)| *n == name)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mock;
+
+    #[test]
+    fn reads_fields_by_name() {
+        mock::with_host(|h| {
+            let p = h.spawn(1, "Game.exe");
+            let mut player = [0; 0x30];
+            player[0x10..0x14].copy_from_slice(&100u32.to_le_bytes());
+            player[0x20..0x24].copy_from_slice(&2.5f32.to_le_bytes());
+            player[0x28] = 1;
+            p.map(0x1000, player);
+        });
+        let p = Process::attach("Game.exe").unwrap();
+        let mut player = StructView::new(0x1000)
+            .with_field("health", 0x10, 4)
+            .with_field("x", 0x20, 4)
+            .with_field("dead", 0x28, 1);
+        assert_eq!(player.field::<u32>(&p, "health"), Ok(100));
+        assert_eq!(player.field::<f32>(&p, "x"), Ok(2.5));
+        assert_eq!(player.field::<u8>(&p, "dead"), Ok(1));
+        assert_eq!(player.address("x"), Some(0x1020));
+        assert_eq!(
+            player.field::<u32>(&p, "y"),
+            Err(Error::FieldNotFound("y".into()))
+        );
+        assert_eq!(
+            player.field::<u64>(&p, "health"),
+            Err(Error::WrongFieldSize {
+                name: "health".into(),
+                size: 4
+            })
+        );
+        player.set_base(0x2000);
+        assert!(player.field::<u32>(&p, "health").is_err());
+    }
+}
@@ -0,0 +1,196 @@
+use alloc::vec::Vec;
+
+use crate::{Address, Pod, Process};
+
+/// Keeps track of the value at an address across ticks so you can tell when
+/// it changes. Call [`update`](Watcher::update) once per tick, after which
+/// [`current`](Watcher::current) is the value read this tick and
+/// [`old`](Watcher::old) is the value from the tick before.
+#[derive(Debug, Clone)]
+pub struct Watcher<T> {
+    addr: Address,
+    offsets: Vec<i64>,
+    old: Option<T>,
+    current: Option<T>,
+}
+
+impl<T: Pod + PartialEq> Watcher<T> {
+    /// Watch the value at `addr`. Nothing is read until the first update.
+    pub const fn new(addr: Address) -> Self {
+        Self {
+            addr,
+            offsets: Vec::new(),
+            old: None,
+            current: None,
+        }
+    }
+
+    /// Watch the value at the end of a pointer path, which is followed again
+    /// on every update like [`read_at_offsets`](Process::read_at_offsets).
+    pub fn from_path(base: Address, offsets: &[i64]) -> Self {
+        Self {
+            offsets: offsets.to_vec(),
+            ..Self::new(base)
+        }
+    }
+
+    /// Read the latest value. If the read fails the current value becomes
+    /// `None`.
+    pub fn update(&mut self, process: &Process) -> Option<T> {
+        self.old = self.current;
+        self.current = process.read_at_offsets(self.addr, &self.offsets).ok();
+        self.current
+    }
+
+    /// The value read on the latest update.
+    pub fn current(&self) -> Option<T> {
+        self.current
+    }
+
+    /// The value read on the update before the latest one.
+    pub fn old(&self) -> Option<T> {
+        self.old
+    }
+
+    /// Whether the value is different than it was last tick. This is only
+    /// `true` if both reads succeeded.
+    pub fn changed(&self) -> bool {
+        matches!((self.old, self.current), (Some(old), Some(current)) if old != current)
+    }
+
+    /// Whether the value just changed to `value`.
+    pub fn changed_to(&self, value: T) -> bool {
+        self.changed() && self.current == Some(value)
+    }
+
+    /// Whether the value just changed from `value` to something else.
+    pub fn changed_from(&self, value: T) -> bool {
+        self.changed() && self.old == Some(value)
+    }
+}
+
+impl<T: Pod + PartialOrd> Watcher<T> {
+    /// Whether the value went up since last tick.
+    pub fn increased(&self) -> bool {
+        matches!((self.old, self.current), (Some(old), Some(current)) if current > old)
+    }
+
+    /// Whether the value went down since last tick.
+    pub fn decreased(&self) -> bool {
+        matches!((self.old, self.current), (Some(old), Some(current)) if current < old)
+    }
+}
+
+/// A group of named [`Watcher`]s of the same type that all get updated
+/// together, which is nicer than a field per watcher when there are a lot of
+/// them.
+///
+/// ```no_run
+/// # use livesplit_wrapper::{Process, WatcherSet};
+/// # fn f(process: &Process) {
+/// let mut counters = WatcherSet::<u32>::new(&[("deaths", 0x1000), ("dashes", 0x1004)]);
+/// counters.update(process);
+/// if counters.changed("deaths") {
+///     log::info!("died! ({:?} total)", counters.current("deaths"));
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct WatcherSet<T> {
+    watchers: Vec<(&'static str, Watcher<T>)>,
+}
+
+impl<T: Pod + PartialEq> WatcherSet<T> {
+    /// Create a watcher for each `(name, address)` pair.
+    pub fn new(addrs: &[(&'static str, Address)]) -> Self {
+        let watchers = addrs
+            .iter()
+            .map(|&(name, addr)| (name, Watcher::new(addr)))
+            .collect();
+        Self { watchers }
+    }
+
+    /// Add another watcher to the set.
+    pub fn insert(&mut self, name: &'static str, addr: Address) {
+        self.watchers.push((name, Watcher::new(addr)));
+    }
+
+    /// Update every watcher in the set.
+    pub fn update(&mut self, process: &Process) {
+        for (_, watcher) in &mut self.watchers {
+            watcher.update(process);
+        }
+    }
+
+    /// Get the watcher with the given name.
+    pub fn get(&self, name: &str) -> Option<&Watcher<T>> {
+        self.watchers
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, w)| w)
+    }
+
+    /// The current value of the named watcher, or `None` if there's no watcher
+    /// with that name or its last read failed.
+    pub fn current(&self, name: &str) -> Option<T> {
+        self.get(name)?.current()
+    }
+
+    /// Whether the named watcher's value changed on the last update.
+    pub fn changed(&self, name: &str) -> bool {
+        self.get(name).is_some_and(Watcher::changed)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mock;
+
+    #[test]
+    fn watcher_predicates() {
+        mock::with_host(|h| {
+            let p = h.spawn(1, "Game.exe");
+            p.map(0x100, 0x200u64.to_le_bytes());
+            p.map(0x210, 5i32.to_le_bytes());
+        });
+        let p = Process::attach("Game.exe").unwrap();
+        let mut level = Watcher::<i32>::from_path(0x100, &[0x10]);
+        let mut step = |value: i32| {
+            mock::with_host(|h| h.process(1).write(0x210, &value.to_le_bytes()));
+            level.update(&p);
+            level.clone()
+        };
+        let first = step(5);
+        assert!(!first.changed() && !first.increased() && !first.decreased());
+        let up = step(7);
+        assert!(up.changed_to(7) && up.changed_from(5) && up.increased());
+        assert!(!up.changed_to(5) && !up.decreased());
+        let same = step(7);
+        assert!(!same.changed_to(7) && !same.increased());
+        let down = step(-1);
+        assert!(down.decreased() && down.changed_from(7));
+    }
+
+    #[test]
+    fn watcher_set() {
+        mock::with_host(|h| h.spawn(1, "Game.exe").map(0x100, [0, 0, 0, 0]));
+        let p = Process::attach("Game.exe").unwrap();
+        let mut set = WatcherSet::<u8>::new(&[("kills", 0x100), ("items", 0x101)]);
+        set.insert("deaths", 0x102);
+        set.update(&p);
+        assert!(!set.changed("kills"));
+
+        mock::with_host(|h| h.process(1).write(0x100, &[1, 0, 3]));
+        set.update(&p);
+        assert!(set.changed("kills"));
+        assert!(!set.changed("items"));
+        assert!(set.changed("deaths"));
+        assert_eq!(set.current("deaths"), Some(3));
+        assert_eq!(set.get("deaths").unwrap().old(), Some(0));
+        assert!(!set.changed("missing"));
+
+        set.update(&p);
+        assert!(!set.changed("kills"));
+    }
+}